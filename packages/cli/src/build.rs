@@ -3,7 +3,9 @@ use tangram_client as tg;
 
 pub mod cancel;
 pub mod children;
+pub mod explain;
 pub mod get;
+pub mod list;
 pub mod log;
 pub mod outcome;
 pub mod output;
@@ -33,7 +35,9 @@ pub struct Args {
 pub enum Command {
 	Cancel(self::cancel::Args),
 	Children(self::children::Args),
+	Explain(self::explain::Args),
 	Get(self::get::Args),
+	List(self::list::Args),
 	Log(self::log::Args),
 	Outcome(self::outcome::Args),
 	Output(self::output::Args),
@@ -55,9 +59,15 @@ impl Cli {
 			Some(Command::Children(args)) => {
 				self.command_build_children(args).await?;
 			},
+			Some(Command::Explain(args)) => {
+				self.command_build_explain(args).await?;
+			},
 			Some(Command::Get(args)) => {
 				self.command_build_get(args).await?;
 			},
+			Some(Command::List(args)) => {
+				self.command_build_list(args).await?;
+			},
 			Some(Command::Log(args)) => {
 				self.command_build_log(args).await?;
 			},
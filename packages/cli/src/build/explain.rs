@@ -0,0 +1,62 @@
+use crate::Cli;
+use tangram_client::{self as tg, Handle as _};
+use time::format_description::well_known::Rfc3339;
+
+/// Explain whether building a target would reuse an existing build.
+#[derive(Clone, Debug, clap::Args)]
+#[group(skip)]
+pub struct Args {
+	/// If this flag is set, the output will be printed as JSON.
+	#[arg(long)]
+	pub json: bool,
+
+	/// The retry strategy to explain the decision for.
+	#[arg(long, default_value = "canceled")]
+	pub retry: tg::build::Retry,
+
+	#[arg(index = 1)]
+	pub target: tg::target::Id,
+}
+
+impl Cli {
+	pub async fn command_build_explain(&self, args: Args) -> tg::Result<()> {
+		let handle = self.handle().await?;
+
+		// Explain the target.
+		let arg = tg::target::explain::Arg { retry: args.retry };
+		let output = handle.explain_target(&args.target, arg).await?;
+
+		// Print the explanation.
+		if args.json {
+			Self::output_json(&output).await?;
+			return Ok(());
+		}
+		match &output.decision {
+			tg::target::explain::Decision::Local { build } => {
+				println!("local hit: {build}");
+			},
+			tg::target::explain::Decision::Remote { build, remote } => {
+				println!("remote hit: {build} on {remote}");
+			},
+			tg::target::explain::Decision::New => {
+				println!("new build");
+			},
+		}
+		println!("{}", output.reason);
+		for build in &output.builds {
+			let created_at = build.created_at.format(&Rfc3339).unwrap();
+			let outcome = match &build.outcome {
+				None => "-",
+				Some(tg::build::outcome::Data::Canceled) => "canceled",
+				Some(tg::build::outcome::Data::Failed(_)) => "failed",
+				Some(tg::build::outcome::Data::Succeeded(_)) => "succeeded",
+			};
+			println!(
+				"{} {} {outcome} retry={} {created_at}",
+				build.id, build.status, build.retry
+			);
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,97 @@
+use crate::Cli;
+use std::time::Duration;
+use tangram_client::{self as tg, Handle as _};
+use time::format_description::well_known::Rfc3339;
+
+/// List builds.
+#[derive(Clone, Debug, clap::Args)]
+#[group(skip)]
+pub struct Args {
+	/// Only list builds for this host.
+	#[arg(long)]
+	pub host: Option<String>,
+
+	/// If this flag is set, the output will be printed as JSON.
+	#[arg(long)]
+	pub json: bool,
+
+	/// The maximum number of builds to list.
+	#[arg(long, default_value = "100")]
+	pub length: u64,
+
+	/// The number of builds to skip.
+	#[arg(long)]
+	pub offset: Option<u64>,
+
+	/// Only list finished builds with this outcome.
+	#[arg(long)]
+	pub outcome: Option<tg::build::outcome::Kind>,
+
+	#[allow(clippy::option_option)]
+	#[arg(short, long)]
+	pub remote: Option<Option<String>>,
+
+	/// Only list builds created within this many seconds.
+	#[arg(long)]
+	pub since: Option<f64>,
+
+	/// Only list builds with this status.
+	#[arg(long)]
+	pub status: Option<tg::build::Status>,
+
+	/// Only list builds of this target.
+	#[arg(long)]
+	pub target: Option<tg::target::Id>,
+}
+
+impl Cli {
+	pub async fn command_build_list(&self, args: Args) -> tg::Result<()> {
+		let handle = self.handle().await?;
+
+		// Get the remote.
+		let remote = args
+			.remote
+			.map(|option| option.unwrap_or_else(|| "default".to_owned()));
+
+		// Get the time after which builds must have been created.
+		let created_after = args
+			.since
+			.map(|since| {
+				let since = Duration::try_from_secs_f64(since)
+					.map_err(|source| tg::error!(!source, "invalid duration"))?;
+				let since = time::Duration::try_from(since)
+					.map_err(|source| tg::error!(!source, "invalid duration"))?;
+				Ok::<_, tg::Error>(time::OffsetDateTime::now_utc() - since)
+			})
+			.transpose()?;
+
+		// List the builds.
+		let arg = tg::build::list::Arg {
+			created_after,
+			created_before: None,
+			host: args.host,
+			length: Some(args.length),
+			offset: args.offset,
+			outcome: args.outcome,
+			remote,
+			status: args.status,
+			target: args.target,
+		};
+		let output = handle.list_builds(arg).await?;
+
+		// Print the builds.
+		if args.json {
+			Self::output_json(&output).await?;
+		} else {
+			for output in output.data {
+				let created_at = output.created_at.format(&Rfc3339).unwrap();
+				println!(
+					"{} {} {} {created_at}",
+					output.id, output.status, output.target
+				);
+			}
+		}
+
+		Ok(())
+	}
+}
@@ -1,4 +1,8 @@
-use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	path::PathBuf,
+	time::Duration,
+};
 use tangram_client::{self as tg, util::serde::is_false};
 use url::Url;
 
@@ -15,6 +19,10 @@ pub struct Config {
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub authentication: Option<Authentication>,
 
+	/// Configure authorization. If this is set, then every request must be made with a token whose scopes permit it, unless the scopes are granted to anonymous requests.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub authorization: Option<Authorization>,
+
 	/// Configure builds.
 	#[allow(clippy::option_option)]
 	#[serde(
@@ -42,6 +50,10 @@ pub struct Config {
 	)]
 	pub build_indexer: Option<Option<BuildIndexer>>,
 
+	/// Configure TLS for a client's connection to the server, such as a private certificate authority or a client certificate.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub client_tls: Option<ClientTls>,
+
 	/// Configure the database.
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub database: Option<Database>,
@@ -72,6 +84,18 @@ pub struct Config {
 	)]
 	pub remotes: Option<Option<BTreeMap<String, Option<Remote>>>>,
 
+	/// Configure external runtimes, keyed by host. An external runtime takes precedence over a built in runtime for the same host.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub runtimes: Option<BTreeMap<String, Runtime>>,
+
+	/// Configure TLS. This is required if the URL's scheme is `https`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub tls: Option<Tls>,
+
+	/// The token a client will use to authenticate with the server. The `TANGRAM_TOKEN` environment variable takes precedence over this, and the credentials file is used if neither is set.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub token: Option<String>,
+
 	/// Configure tracing.
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub tracing: Option<Tracing>,
@@ -155,12 +179,26 @@ pub struct AuthenticationProviders {
 	pub github: Option<Oauth>,
 }
 
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct Authorization {
+	/// The scopes granted to requests made without a token.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub anonymous: Option<BTreeSet<tg::user::Scope>>,
+}
+
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub struct Oauth {
+	/// The URL of the provider's API. The default is GitHub's API.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub api_url: Option<String>,
+
 	pub auth_url: String,
 	pub client_id: String,
 	pub client_secret: String,
+
+	/// The URL the provider redirects to after the user authorizes the login. This must be the server's `/oauth/github` route.
 	pub redirect_url: String,
+
 	pub token_url: String,
 }
 
@@ -173,6 +211,51 @@ pub struct Build {
 	/// The heartbeat interval, in seconds. Builds will send a heartbeat at this interval.
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub heartbeat_interval: Option<f64>,
+
+	/// The labels of this server's workers. Only builds whose labels are all in this set will be dequeued.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub labels: Option<Vec<String>>,
+
+	/// Resource limits for sandboxed builds. On Linux, limits are enforced with cgroups v2, and setting any limit moves the server's process into a new child of its cgroup named `server`. Limits are not enforced on other platforms, and a warning is logged if they are set.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub limits: Option<BuildLimits>,
+
+	/// Additional paths from the host to bind mount into sandboxed builds on Linux. Mounts are configured for the server rather than per target. They make builds depend on the host, so like the network, they are only available to builds whose targets have checksums, and if any are set, then sandboxed builds whose targets do not have checksums fail. Mounts are not supported on other platforms, and a warning is logged if they are set.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub mounts: Option<Vec<BuildMount>>,
+
+	/// The default timeout for builds, in seconds. Builds that run longer than this will fail.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub timeout: Option<f64>,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct BuildLimits {
+	/// The maximum number of CPUs a build may use.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub cpu: Option<f64>,
+
+	/// The maximum amount of memory a build may use, in bytes.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub memory: Option<u64>,
+
+	/// The maximum number of processes a build may run at once.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub pids: Option<u64>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct BuildMount {
+	/// The path on the host.
+	pub source: PathBuf,
+
+	/// The absolute path in the sandbox. The default is the source path.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub target: Option<PathBuf>,
+
+	/// Whether the mount is read-only. The default is `true`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub readonly: Option<bool>,
 }
 
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
@@ -229,6 +312,59 @@ pub struct Remote {
 	/// Enable remote builds.
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub build: Option<bool>,
+
+	/// Configure TLS for the connection to the remote.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub tls: Option<ClientTls>,
+
+	/// The token to authenticate with the remote.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub token: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Tls {
+	/// The path to the server's certificate chain in PEM format.
+	pub certificate: PathBuf,
+
+	/// The path to the server's private key in PEM format.
+	pub key: PathBuf,
+
+	/// The path to a PEM file of certificate authorities. If this is set, then clients must present a certificate signed by one of them.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub client_certificate_authorities: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct ClientTls {
+	/// The path to a PEM file of certificate authorities to trust in addition to the web PKI roots.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub certificate_authorities: Option<PathBuf>,
+
+	/// The path to the client's certificate chain in PEM format. This must be set along with `key`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub certificate: Option<PathBuf>,
+
+	/// The path to the client's private key in PEM format.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub key: Option<PathBuf>,
+}
+
+/// The credentials written by `tg login`.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Credentials {
+	/// The token to authenticate with the server.
+	pub token: String,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Runtime {
+	/// The path to the runtime's executable. It is run with the build's ID as its last argument and with `TANGRAM_URL` set to a URL for a server that it can use to get the build's target and store objects.
+	pub executable: PathBuf,
+
+	/// Additional arguments to pass to the executable before the build's ID.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub args: Vec<String>,
 }
 
 #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
@@ -0,0 +1,66 @@
+use crate::{config::Credentials, Cli};
+use std::time::Duration;
+use tangram_client::{self as tg, Handle as _};
+
+/// The duration to wait for the login to finish.
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Log in to the server.
+#[derive(Clone, Debug, clap::Args)]
+#[group(skip)]
+pub struct Args {
+	/// Print the login URL instead of opening it in a browser.
+	#[arg(long)]
+	pub no_browser: bool,
+}
+
+impl Cli {
+	pub async fn command_login(&self, args: Args) -> tg::Result<()> {
+		let handle = self.handle().await?;
+
+		// Create the login.
+		let login = handle.create_login().await?;
+
+		// Open the login URL in the browser, or print it if that fails.
+		let url = login.url.to_string();
+		if args.no_browser || webbrowser::open(&url).is_err() {
+			eprintln!("To log in, visit {url}");
+		} else {
+			eprintln!("Your browser has been opened to {url}");
+		}
+
+		// Poll for the login's token until the login finishes or times out.
+		let secret = login
+			.secret
+			.ok_or_else(|| tg::error!("expected the login to have a secret"))?;
+		let poll = async {
+			loop {
+				let arg = tg::login::get::Arg {
+					secret: secret.clone(),
+				};
+				let login = handle
+					.try_get_login(&login.id, arg)
+					.await?
+					.ok_or_else(|| tg::error!("the login has expired"))?;
+				if let Some(source) = login.error {
+					return Err(tg::error!(!source, "the login failed"));
+				}
+				if let Some(token) = login.token {
+					return Ok::<_, tg::Error>(token);
+				}
+				tokio::time::sleep(Duration::from_secs(1)).await;
+			}
+		};
+		let token = tokio::time::timeout(LOGIN_TIMEOUT, poll)
+			.await
+			.map_err(|_| tg::error!("the login timed out"))??;
+
+		// Write the credentials.
+		let credentials = Credentials { token };
+		Self::write_credentials(&credentials)?;
+
+		eprintln!("You have been logged in.");
+
+		Ok(())
+	}
+}
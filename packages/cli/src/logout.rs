@@ -0,0 +1,27 @@
+use crate::Cli;
+use tangram_client::{self as tg, Handle as _};
+
+/// Log out of the server.
+#[derive(Clone, Debug, clap::Args)]
+#[group(skip)]
+pub struct Args {}
+
+impl Cli {
+	pub async fn command_logout(&self, _args: Args) -> tg::Result<()> {
+		// Read the credentials.
+		let Some(credentials) = Self::read_credentials()? else {
+			eprintln!("You are not logged in.");
+			return Ok(());
+		};
+
+		// Revoke the token.
+		let handle = self.handle().await?;
+		handle.delete_token(&credentials.token).await?;
+
+		// Remove the credentials.
+		Self::remove_credentials()?;
+
+		eprintln!("You have been logged out.");
+		Ok(())
+	}
+}
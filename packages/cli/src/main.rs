@@ -1,4 +1,4 @@
-use self::config::{Config, DEFAULT_FILE_DESCRIPTOR_SEMAPHORE_SIZE};
+use self::config::{Config, Credentials, DEFAULT_FILE_DESCRIPTOR_SEMAPHORE_SIZE};
 use clap::{CommandFactory as _, Parser as _};
 use crossterm::{style::Stylize as _, tty::IsTty as _};
 use futures::FutureExt as _;
@@ -18,6 +18,8 @@ mod cat;
 mod checksum;
 mod config;
 mod get;
+mod login;
+mod logout;
 mod lsp;
 mod object;
 mod package;
@@ -25,6 +27,7 @@ mod progress;
 mod pull;
 mod push;
 mod remote;
+mod root;
 mod server;
 mod tag;
 mod tangram;
@@ -112,6 +115,8 @@ enum Command {
 	Init(self::package::init::Args),
 	List(self::tag::list::Args),
 	Log(self::build::log::Args),
+	Login(self::login::Args),
+	Logout(self::logout::Args),
 	Lsp(self::lsp::Args),
 	New(self::package::new::Args),
 	Object(self::object::Args),
@@ -121,6 +126,7 @@ enum Command {
 	Push(self::push::Args),
 	Put(self::object::put::Args),
 	Remote(self::remote::Args),
+	Root(self::root::Args),
 	Run(self::target::run::Args),
 	Serve(self::server::run::Args),
 	Server(self::server::Args),
@@ -265,7 +271,9 @@ impl Cli {
 			});
 
 		// Create the client.
-		let client = tg::Client::new(url);
+		let token = self.token()?;
+		let tls = self.client_tls();
+		let client = tg::Client::with_tls(url, token, tls);
 
 		// Attempt to connect to the server.
 		client.connect().await.ok();
@@ -354,7 +362,9 @@ impl Cli {
 			});
 
 		// Create the client.
-		let client = tg::Client::new(url);
+		let token = self.token()?;
+		let tls = self.client_tls();
+		let client = tg::Client::with_tls(url, token, tls);
 
 		// Attempt to connect to the server.
 		client.connect().await.ok();
@@ -439,6 +449,7 @@ impl Cli {
 					let providers = authentication.providers.as_ref().map(|providers| {
 						let github = providers.github.as_ref().map(|client| {
 							tangram_server::options::Oauth {
+								api_url: client.api_url.clone(),
 								auth_url: client.auth_url.clone(),
 								client_id: client.client_id.clone(),
 								client_secret: client.client_secret.clone(),
@@ -452,6 +463,16 @@ impl Cli {
 				})
 				.unwrap_or_default();
 
+		// Create the authorization options.
+		let authorization = self
+			.config
+			.as_ref()
+			.and_then(|config| config.authorization.as_ref())
+			.map(|authorization| {
+				let anonymous = authorization.anonymous.clone().unwrap_or_default();
+				tangram_server::options::Authorization { anonymous }
+			});
+
 		// Create the build options.
 		let build = match self.config.as_ref().and_then(|config| config.build.clone()) {
 			None => Some(crate::config::Build::default()),
@@ -465,9 +486,31 @@ impl Cli {
 			let heartbeat_interval = build
 				.heartbeat_interval
 				.map_or(Duration::from_secs(1), Duration::from_secs_f64);
+			let labels = build.labels.unwrap_or_default().into_iter().collect();
+			let limits = build.limits.unwrap_or_default();
+			let limits = tangram_server::options::BuildLimits {
+				cpu: limits.cpu,
+				memory: limits.memory,
+				pids: limits.pids,
+			};
+			let mounts = build
+				.mounts
+				.unwrap_or_default()
+				.into_iter()
+				.map(|mount| tangram_server::options::BuildMount {
+					target: mount.target.unwrap_or_else(|| mount.source.clone()),
+					source: mount.source,
+					readonly: mount.readonly.unwrap_or(true),
+				})
+				.collect();
+			let timeout = build.timeout.map(Duration::from_secs_f64);
 			tangram_server::options::Build {
 				concurrency,
 				heartbeat_interval,
+				labels,
+				limits,
+				mounts,
+				timeout,
 			}
 		});
 
@@ -599,7 +642,13 @@ impl Cli {
 							let name = name.clone();
 							let build = remote.build.unwrap_or_default();
 							let url = remote.url.clone();
-							let client = tg::Client::new(url);
+							let token = remote.token.clone();
+							let tls = remote.tls.clone().map(|tls| tg::Tls {
+								certificate_authorities: tls.certificate_authorities,
+								certificate: tls.certificate,
+								key: tls.key,
+							});
+							let client = tg::Client::with_tls(url, token, tls);
 							let remote = tangram_server::options::Remote { build, client };
 							remotes.insert(name, remote);
 						},
@@ -608,6 +657,33 @@ impl Cli {
 			},
 		}
 
+		// Create the runtime options.
+		let runtimes = self
+			.config
+			.as_ref()
+			.and_then(|config| config.runtimes.clone())
+			.unwrap_or_default()
+			.into_iter()
+			.map(|(host, runtime)| {
+				let runtime = tangram_server::options::Runtime {
+					executable: runtime.executable,
+					args: runtime.args,
+				};
+				(host, runtime)
+			})
+			.collect();
+
+		// Create the TLS options.
+		let tls = self
+			.config
+			.as_ref()
+			.and_then(|config| config.tls.clone())
+			.map(|tls| tangram_server::options::Tls {
+				certificate: tls.certificate,
+				key: tls.key,
+				client_certificate_authorities: tls.client_certificate_authorities,
+			});
+
 		// Get the version.
 		let version = Some(crate::Args::command().get_version().unwrap().to_owned());
 
@@ -639,6 +715,7 @@ impl Cli {
 		let options = tangram_server::Options {
 			advanced,
 			authentication,
+			authorization,
 			build,
 			build_heartbeat_monitor,
 			build_indexer,
@@ -647,6 +724,8 @@ impl Cli {
 			object_indexer,
 			path,
 			remotes,
+			runtimes,
+			tls,
 			url,
 			version,
 			vfs,
@@ -791,6 +870,8 @@ impl Cli {
 			Command::Init(args) => self.command_package_init(args).boxed(),
 			Command::List(args) => self.command_tag_list(args).boxed(),
 			Command::Log(args) => self.command_build_log(args).boxed(),
+			Command::Login(args) => self.command_login(args).boxed(),
+			Command::Logout(args) => self.command_logout(args).boxed(),
 			Command::Lsp(args) => self.command_lsp(args).boxed(),
 			Command::New(args) => self.command_package_new(args).boxed(),
 			Command::Object(args) => self.command_object(args).boxed(),
@@ -800,6 +881,7 @@ impl Cli {
 			Command::Push(args) => self.command_push(args).boxed(),
 			Command::Put(args) => self.command_object_put(args).boxed(),
 			Command::Remote(args) => self.command_remote(args).boxed(),
+			Command::Root(args) => self.command_root(args).boxed(),
 			Command::Run(args) => self.command_target_run(args).boxed(),
 			Command::Serve(args) => self.command_server_run(args).boxed(),
 			Command::Server(args) => self.command_server(args).boxed(),
@@ -832,6 +914,84 @@ impl Cli {
 		Ok(Some(config))
 	}
 
+	/// Get the token to authenticate with the server from the `TANGRAM_TOKEN` environment variable, the config, or the credentials file, in that order.
+	fn token(&self) -> tg::Result<Option<String>> {
+		if let Ok(token) = std::env::var("TANGRAM_TOKEN") {
+			return Ok(Some(token));
+		}
+		if let Some(token) = self.config.as_ref().and_then(|config| config.token.clone()) {
+			return Ok(Some(token));
+		}
+		let credentials = Self::read_credentials()?;
+		Ok(credentials.map(|credentials| credentials.token))
+	}
+
+	fn client_tls(&self) -> Option<tg::Tls> {
+		self.config
+			.as_ref()
+			.and_then(|config| config.client_tls.clone())
+			.map(|tls| tg::Tls {
+				certificate_authorities: tls.certificate_authorities,
+				certificate: tls.certificate,
+				key: tls.key,
+			})
+	}
+
+	fn read_credentials() -> tg::Result<Option<Credentials>> {
+		let path = Self::credentials_path();
+		let credentials = match std::fs::read_to_string(&path) {
+			Ok(credentials) => credentials,
+			Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+			Err(source) => {
+				return Err(
+					tg::error!(!source, %path = path.display(), "failed to read the credentials file"),
+				)
+			},
+		};
+		let credentials = serde_json::from_str(&credentials).map_err(
+			|source| tg::error!(!source, %path = path.display(), "failed to deserialize the credentials"),
+		)?;
+		Ok(Some(credentials))
+	}
+
+	fn write_credentials(credentials: &Credentials) -> tg::Result<()> {
+		use std::{io::Write as _, os::unix::fs::OpenOptionsExt as _};
+		let path = Self::credentials_path();
+		std::fs::create_dir_all(path.parent().unwrap()).map_err(
+			|source| tg::error!(!source, %path = path.display(), "failed to create the config directory"),
+		)?;
+		let credentials = serde_json::to_string_pretty(credentials)
+			.map_err(|source| tg::error!(!source, "failed to serialize the credentials"))?;
+		let mut file = std::fs::OpenOptions::new()
+			.create(true)
+			.truncate(true)
+			.write(true)
+			.mode(0o600)
+			.open(&path)
+			.map_err(
+				|source| tg::error!(!source, %path = path.display(), "failed to open the credentials file"),
+			)?;
+		file.write_all(credentials.as_bytes()).map_err(
+			|source| tg::error!(!source, %path = path.display(), "failed to write the credentials file"),
+		)?;
+		Ok(())
+	}
+
+	fn remove_credentials() -> tg::Result<()> {
+		let path = Self::credentials_path();
+		match std::fs::remove_file(&path) {
+			Ok(()) => Ok(()),
+			Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+			Err(source) => Err(
+				tg::error!(!source, %path = path.display(), "failed to remove the credentials file"),
+			),
+		}
+	}
+
+	fn credentials_path() -> PathBuf {
+		PathBuf::from(std::env::var("HOME").unwrap()).join(".config/tangram/credentials.json")
+	}
+
 	fn _write_config(config: &Config, path: Option<PathBuf>) -> tg::Result<()> {
 		let path = path.unwrap_or_else(|| {
 			PathBuf::from(std::env::var("HOME").unwrap()).join(".config/tangram/config.json")
@@ -1,20 +1,191 @@
 use crate::Cli;
-use tangram_client as tg;
+use futures::TryStreamExt as _;
+use std::collections::BTreeSet;
+use tangram_client::{self as tg, Handle as _};
+use tangram_semver as semver;
 
 /// Get a package's outdated dependencies.
 #[derive(Clone, Debug, clap::Args)]
 #[group(skip)]
 pub struct Args {
+	/// If this flag is set, the output will be printed as JSON.
+	#[arg(long)]
+	pub json: bool,
+
 	/// If this flag is set, the package's lockfile will not be updated.
 	#[arg(long)]
 	pub locked: bool,
 
 	#[arg(index = 1, default_value = ".")]
 	pub package: tg::Reference,
+
+	#[allow(clippy::option_option)]
+	#[arg(short, long)]
+	pub remote: Option<Option<String>>,
+}
+
+/// An outdated dependency.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct Output {
+	pub reference: tg::Reference,
+
+	pub current: Option<tg::Tag>,
+
+	pub compatible: Option<tg::Tag>,
+
+	pub latest: Option<tg::Tag>,
 }
 
 impl Cli {
-	pub async fn command_package_outdated(&self, _args: Args) -> tg::Result<()> {
-		Err(tg::error!("unimplemented"))
+	pub async fn command_package_outdated(&self, args: Args) -> tg::Result<()> {
+		let handle = self.handle().await?;
+
+		// Get the remote.
+		let remote = args
+			.remote
+			.map(|option| option.unwrap_or_else(|| "default".to_owned()));
+
+		// Get the package's path.
+		let tg::reference::Path::Path(path) = args.package.path() else {
+			return Err(tg::error!("expected a path"));
+		};
+		let path = tokio::fs::canonicalize(&path)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to canonicalize the path"))?;
+
+		// Check in the package to update its lockfile.
+		if !args.locked {
+			let arg = tg::artifact::checkin::Arg {
+				destructive: false,
+				deterministic: false,
+				ignore: true,
+				locked: false,
+				path: path.clone(),
+			};
+			let stream = handle.check_in_artifact(arg).await?;
+			stream.map_ok(|_| ()).try_collect::<()>().await?;
+		}
+
+		// Read the lockfile.
+		let lockfile_path = path.join(tg::package::LOCKFILE_FILE_NAME);
+		let lockfile = tg::Lockfile::try_read(&lockfile_path).await?.ok_or_else(
+			|| tg::error!(%path = lockfile_path.display(), "failed to find the lockfile"),
+		)?;
+
+		// Collect the tagged dependencies.
+		let mut dependencies = BTreeSet::new();
+		for node in &lockfile.nodes {
+			let tg::lockfile::Node::File {
+				dependencies: file_dependencies,
+				..
+			} = node
+			else {
+				continue;
+			};
+			for (reference, dependency) in file_dependencies {
+				let tg::reference::Path::Tag(pattern) = reference.path() else {
+					continue;
+				};
+				dependencies.insert((pattern.clone(), dependency.tag.clone()));
+			}
+		}
+
+		// Compare each dependency's current tag with the tags that match its pattern.
+		let mut outputs = Vec::new();
+		for (pattern, current) in dependencies {
+			// Find the semver component of the pattern. Dependencies without one cannot be outdated.
+			let Some((index, version_pattern)) =
+				pattern
+					.components()
+					.iter()
+					.enumerate()
+					.find_map(|(index, component)| {
+						let pattern = component.try_unwrap_semver_ref().ok()?;
+						Some((index, pattern.clone()))
+					})
+			else {
+				continue;
+			};
+
+			// List all versions of the dependency by replacing the semver component with a glob.
+			let components = pattern
+				.components()
+				.iter()
+				.map(|component| match component {
+					tg::tag::pattern::Component::Semver(_) => tg::tag::pattern::Component::Glob,
+					component => component.clone(),
+				})
+				.collect();
+			let arg = tg::tag::list::Arg {
+				length: None,
+				pattern: tg::tag::Pattern::with_components(components),
+				remote: remote.clone(),
+			};
+			let output = handle.list_tags(arg).await?;
+			let versions = output
+				.data
+				.into_iter()
+				.filter_map(|output| {
+					let version = output
+						.tag
+						.components()
+						.get(index)?
+						.as_str()
+						.parse::<semver::Version>()
+						.ok()?;
+					Some((version, output.tag))
+				})
+				.collect::<Vec<_>>();
+
+			// Find the latest compatible and the latest versions.
+			let compatible = versions
+				.iter()
+				.filter(|(version, _)| version_pattern.matches(version))
+				.max_by(|(a, _), (b, _)| a.cmp(b))
+				.map(|(_, tag)| tag.clone());
+			let latest = versions
+				.iter()
+				.max_by(|(a, _), (b, _)| a.cmp(b))
+				.map(|(_, tag)| tag.clone());
+
+			// Skip the dependency if it is up to date.
+			if current == compatible && current == latest {
+				continue;
+			}
+
+			let reference = tg::Reference::with_tag(&pattern)?;
+			outputs.push(Output {
+				reference,
+				current,
+				compatible,
+				latest,
+			});
+		}
+
+		// Print the outdated dependencies.
+		if args.json {
+			Self::output_json(&outputs).await?;
+		} else {
+			for output in &outputs {
+				let current = output
+					.current
+					.as_ref()
+					.map_or_else(|| "-".to_owned(), ToString::to_string);
+				let compatible = output
+					.compatible
+					.as_ref()
+					.map_or_else(|| "-".to_owned(), ToString::to_string);
+				let latest = output
+					.latest
+					.as_ref()
+					.map_or_else(|| "-".to_owned(), ToString::to_string);
+				println!(
+					"{} current: {current} compatible: {compatible} latest: {latest}",
+					output.reference
+				);
+			}
+		}
+
+		Ok(())
 	}
 }
@@ -1,7 +1,7 @@
 use crate::Cli;
 use tangram_client::{self as tg, Handle as _};
 
-/// Delete a remote.
+/// Delete a remote. A remote from the config will be added again when the server restarts.
 #[derive(Clone, Debug, clap::Args)]
 #[group(skip)]
 pub struct Args {
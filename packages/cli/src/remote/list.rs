@@ -12,7 +12,10 @@ impl Cli {
 		let arg = tg::remote::list::Arg::default();
 		let remotes = handle.list_remotes(arg).await?;
 		for remote in remotes.data {
-			println!("{} {}", remote.name, remote.url);
+			let source = remote
+				.source
+				.map_or_else(|| "-".to_owned(), |source| source.to_string());
+			println!("{} {} {source}", remote.name, remote.url);
 		}
 		Ok(())
 	}
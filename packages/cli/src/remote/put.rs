@@ -2,13 +2,17 @@ use crate::Cli;
 use tangram_client::{self as tg, Handle as _};
 use url::Url;
 
-/// Put a remote.
+/// Put a remote. The remote is saved in the server's database and takes precedence over a remote with the same name in the config.
 #[derive(Clone, Debug, clap::Args)]
 #[group(skip)]
 pub struct Args {
 	#[arg(index = 1)]
 	pub name: String,
 
+	/// The token to authenticate with the remote.
+	#[arg(long)]
+	pub token: Option<String>,
+
 	#[arg(index = 2)]
 	pub url: Url,
 }
@@ -18,7 +22,8 @@ impl Cli {
 		let handle = self.handle().await?;
 		let name = args.name;
 		let url = args.url;
-		let arg = tg::remote::put::Arg { url };
+		let token = args.token;
+		let arg = tg::remote::put::Arg { url, token };
 		handle.put_remote(&name, arg).await?;
 		Ok(())
 	}
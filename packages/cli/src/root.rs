@@ -0,0 +1,39 @@
+use crate::Cli;
+use tangram_client as tg;
+
+pub mod add;
+pub mod list;
+pub mod remove;
+
+/// Manage roots.
+#[derive(Clone, Debug, clap::Args)]
+#[group(skip)]
+pub struct Args {
+	#[command(subcommand)]
+	pub command: Command,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, clap::Subcommand)]
+pub enum Command {
+	Add(self::add::Args),
+	List(self::list::Args),
+	Remove(self::remove::Args),
+}
+
+impl Cli {
+	pub async fn command_root(&self, args: Args) -> tg::Result<()> {
+		match args.command {
+			Command::Add(args) => {
+				self.command_root_add(args).await?;
+			},
+			Command::List(args) => {
+				self.command_root_list(args).await?;
+			},
+			Command::Remove(args) => {
+				self.command_root_remove(args).await?;
+			},
+		}
+		Ok(())
+	}
+}
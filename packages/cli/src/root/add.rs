@@ -0,0 +1,35 @@
+use crate::Cli;
+use tangram_client::{self as tg, Handle as _};
+use tangram_either::Either;
+
+/// Add a root.
+#[derive(Clone, Debug, clap::Args)]
+#[group(skip)]
+pub struct Args {
+	#[arg(index = 1)]
+	pub name: String,
+
+	#[arg(index = 2, default_value = ".")]
+	pub reference: tg::Reference,
+}
+
+impl Cli {
+	pub async fn command_root_add(&self, args: Args) -> tg::Result<()> {
+		let handle = self.handle().await?;
+
+		// Get the reference.
+		let item = self.get_reference(&args.reference).await?;
+
+		// Get the item.
+		let item = match item {
+			Either::Left(build) => Either::Left(build.id().clone()),
+			Either::Right(object) => Either::Right(object.id(&handle).await?.clone()),
+		};
+
+		// Add the root.
+		let arg = tg::root::put::Arg { item };
+		handle.put_root(&args.name, arg).await?;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,19 @@
+use crate::Cli;
+use tangram_client::{self as tg, Handle as _};
+
+/// List roots.
+#[derive(Clone, Debug, clap::Args)]
+#[group(skip)]
+pub struct Args {}
+
+impl Cli {
+	pub async fn command_root_list(&self, _args: Args) -> tg::Result<()> {
+		let handle = self.handle().await?;
+		let arg = tg::root::list::Arg::default();
+		let roots = handle.list_roots(arg).await?;
+		for root in roots.data {
+			println!("{} {}", root.name, root.item);
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,18 @@
+use crate::Cli;
+use tangram_client::{self as tg, Handle as _};
+
+/// Remove a root.
+#[derive(Clone, Debug, clap::Args)]
+#[group(skip)]
+pub struct Args {
+	#[arg(index = 1)]
+	pub name: String,
+}
+
+impl Cli {
+	pub async fn command_root_remove(&self, args: Args) -> tg::Result<()> {
+		let handle = self.handle().await?;
+		handle.delete_root(&args.name).await?;
+		Ok(())
+	}
+}
@@ -1,15 +1,31 @@
 use crate::Cli;
+use std::time::Duration;
 use tangram_client::{self as tg, Handle as _};
 
-/// Remove unused objects.
+/// Remove unused builds and objects.
 #[derive(Clone, Debug, clap::Args)]
 #[group(skip)]
-pub struct Args {}
+pub struct Args {
+	/// If this flag is set, then nothing will be removed, and the builds and objects that would be removed will be reported.
+	#[arg(long)]
+	pub dry_run: bool,
+
+	/// Keep builds and objects that were touched within this many seconds.
+	#[arg(long)]
+	pub max_age: Option<u64>,
+}
 
 impl Cli {
-	pub async fn command_server_clean(&self, _args: Args) -> tg::Result<()> {
+	pub async fn command_server_clean(&self, args: Args) -> tg::Result<()> {
 		let handle = self.handle().await?;
-		handle.clean().await?;
+		let arg = tg::server::clean::Arg {
+			dry_run: args.dry_run,
+			max_age: args.max_age.map(Duration::from_secs),
+		};
+		let output = handle.clean(arg).await?;
+		let output = serde_json::to_string_pretty(&output)
+			.map_err(|source| tg::error!(!source, "failed to serialize"))?;
+		println!("{output}");
 		Ok(())
 	}
 }
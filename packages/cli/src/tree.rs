@@ -1,5 +1,11 @@
 use crate::Cli;
-use tangram_client as tg;
+use futures::{stream::FuturesOrdered, StreamExt as _, TryStreamExt as _};
+use std::{io::SeekFrom, time::Duration};
+use tangram_client::{self as tg, handle::Ext as _};
+use tangram_either::Either;
+
+/// How long to wait for more children of an unfinished build before printing what has been received.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(1);
 
 /// Display a tree for a build or value.
 #[derive(Clone, Debug, clap::Args)]
@@ -29,8 +35,269 @@ pub struct Tree {
 }
 
 impl Cli {
-	pub async fn command_tree(&self, _args: Args) -> tg::Result<()> {
-		Err(tg::error!("unimplemented"))
+	pub async fn command_tree(&self, args: Args) -> tg::Result<()> {
+		let handle = self.handle().await?;
+
+		// Get the reference.
+		let item = self.get_reference(&args.reference).await?;
+
+		// Create the tree.
+		let tree = match item {
+			Either::Left(build) => {
+				Self::get_build_tree(&handle, &build, args.remote.as_ref(), 0, args.depth).await?
+			},
+			Either::Right(object) => {
+				Self::get_value_tree(&handle, None, object.into(), 0, args.depth).await?
+			},
+		};
+
+		// Print the tree.
+		tree.print();
+
+		Ok(())
+	}
+
+	async fn get_build_tree<H>(
+		handle: &H,
+		build: &tg::Build,
+		remote: Option<&String>,
+		current_depth: u32,
+		max_depth: Option<u32>,
+	) -> tg::Result<Tree>
+	where
+		H: tg::Handle,
+	{
+		// Get the build.
+		let output = handle.get_build(build.id()).await?;
+
+		// Create the title.
+		let mut title = build.id().to_string();
+		let target = build.target(handle).await?;
+		let host = target.host(handle).await?.clone();
+		if matches!(host.as_str(), "builtin" | "js") {
+			let name = target
+				.args(handle)
+				.await?
+				.first()
+				.and_then(|arg| arg.try_unwrap_string_ref().ok())
+				.cloned();
+			if let Some(name) = name {
+				title.push(' ');
+				title.push_str(&name);
+			}
+		}
+		let status = match &output.outcome {
+			Some(tg::build::outcome::Data::Canceled) => "canceled".to_owned(),
+			Some(tg::build::outcome::Data::Failed(_)) => "failed".to_owned(),
+			Some(tg::build::outcome::Data::Succeeded(_)) => "succeeded".to_owned(),
+			None => output.status.to_string(),
+		};
+		title.push(' ');
+		title.push_str(&status);
+
+		// If the maximum depth was reached, then do not render the children.
+		if max_depth.is_some_and(|max_depth| current_depth >= max_depth) {
+			return Ok(Tree {
+				title,
+				children: Vec::new(),
+			});
+		}
+
+		// Get the children. If the build is not finished, then only take the children that are available now.
+		let arg = tg::build::children::get::Arg {
+			position: Some(SeekFrom::Start(0)),
+			remote: remote.cloned(),
+			..Default::default()
+		};
+		let mut stream = build.children(handle, arg).await?.boxed();
+		let children = if output.status == tg::build::Status::Finished {
+			stream.try_collect().await?
+		} else {
+			let mut children = Vec::new();
+			while let Ok(child) = tokio::time::timeout(IDLE_TIMEOUT, stream.try_next()).await {
+				let Some(child) = child? else {
+					break;
+				};
+				children.push(child);
+			}
+			children
+		};
+
+		// Create the children's trees.
+		let children = children
+			.iter()
+			.map(|child| {
+				Box::pin(Self::get_build_tree(
+					handle,
+					child,
+					remote,
+					current_depth + 1,
+					max_depth,
+				))
+			})
+			.collect::<FuturesOrdered<_>>()
+			.try_collect()
+			.await?;
+
+		Ok(Tree { title, children })
+	}
+
+	async fn get_value_tree<H>(
+		handle: &H,
+		name: Option<String>,
+		value: tg::Value,
+		current_depth: u32,
+		max_depth: Option<u32>,
+	) -> tg::Result<Tree>
+	where
+		H: tg::Handle,
+	{
+		// Create the title.
+		let mut title = String::new();
+		if let Some(name) = name {
+			title.push_str(&name);
+			title.push_str(": ");
+		}
+		match &value {
+			tg::Value::Array(_) => title.push_str("array"),
+			tg::Value::Map(_) => title.push_str("map"),
+			tg::Value::Mutation(_) => title.push_str("mutation"),
+			tg::Value::Object(object) => {
+				let id = object.id(handle).await?;
+				title.push_str(&id.to_string());
+			},
+			value => title.push_str(&value.to_string()),
+		}
+
+		// If the maximum depth was reached, then do not render the children.
+		if max_depth.is_some_and(|max_depth| current_depth >= max_depth) {
+			return Ok(Tree {
+				title,
+				children: Vec::new(),
+			});
+		}
+
+		// Create the children's trees.
+		let children = Self::get_value_children(handle, value)
+			.await?
+			.into_iter()
+			.map(|(name, value)| {
+				Box::pin(Self::get_value_tree(
+					handle,
+					name,
+					value,
+					current_depth + 1,
+					max_depth,
+				))
+			})
+			.collect::<FuturesOrdered<_>>()
+			.try_collect()
+			.await?;
+
+		Ok(Tree { title, children })
+	}
+
+	async fn get_value_children<H>(
+		handle: &H,
+		value: tg::Value,
+	) -> tg::Result<Vec<(Option<String>, tg::Value)>>
+	where
+		H: tg::Handle,
+	{
+		let mut children = Vec::new();
+		match value {
+			tg::Value::Array(array) => {
+				children.extend(array.into_iter().map(|value| (None, value)));
+			},
+
+			tg::Value::Map(map) => {
+				children.extend(map.into_iter().map(|(name, value)| (Some(name), value)));
+			},
+
+			tg::Value::Template(template) => {
+				children.extend(
+					template
+						.artifacts()
+						.map(|artifact| (None, tg::Value::Object(artifact.clone().into()))),
+				);
+			},
+
+			tg::Value::Object(tg::Object::Branch(branch)) => {
+				for child in branch.children(handle).await?.iter() {
+					children.push((None, child.blob.clone().into()));
+				}
+			},
+
+			tg::Value::Object(tg::Object::Directory(directory)) => {
+				for (name, artifact) in directory.entries(handle).await? {
+					children.push((Some(name), tg::Value::Object(artifact.into())));
+				}
+			},
+
+			tg::Value::Object(tg::Object::File(file)) => {
+				let contents = file.contents(handle).await?;
+				children.push((Some("contents".into()), contents.into()));
+				for (reference, dependency) in file.dependencies(handle).await? {
+					children.push((Some(reference.to_string()), dependency.object.into()));
+				}
+			},
+
+			tg::Value::Object(tg::Object::Symlink(symlink)) => {
+				if let Some(artifact) = symlink.artifact(handle).await? {
+					children.push((Some("artifact".into()), tg::Value::Object(artifact.into())));
+				}
+				if let Some(path) = symlink.path(handle).await? {
+					children.push((Some("path".into()), path.into()));
+				}
+			},
+
+			tg::Value::Object(tg::Object::Graph(graph)) => {
+				let object = graph.object(handle).await?;
+				for node in &object.nodes {
+					match node {
+						tg::graph::Node::Directory(directory) => {
+							for (name, either) in &directory.entries {
+								if let Either::Right(artifact) = either {
+									children.push((
+										Some(name.clone()),
+										tg::Value::Object(artifact.clone().into()),
+									));
+								}
+							}
+						},
+						tg::graph::Node::File(file) => {
+							children.push((Some("contents".into()), file.contents.clone().into()));
+							for (reference, dependency) in &file.dependencies {
+								if let Either::Right(object) = &dependency.object {
+									children
+										.push((Some(reference.to_string()), object.clone().into()));
+								}
+							}
+						},
+						tg::graph::Node::Symlink(symlink) => {
+							if let Some(Either::Right(artifact)) = &symlink.artifact {
+								children.push((
+									Some("artifact".into()),
+									tg::Value::Object(artifact.clone().into()),
+								));
+							}
+						},
+					}
+				}
+			},
+
+			tg::Value::Object(tg::Object::Target(target)) => {
+				let executable = target.executable(handle).await?.clone();
+				children.push((Some("executable".into()), executable.into()));
+				let args = target.args(handle).await?.clone();
+				children.push((Some("args".into()), args.into()));
+				let env = target.env(handle).await?.clone();
+				children.push((Some("env".into()), env.into()));
+			},
+
+			_ => (),
+		}
+		Ok(children)
 	}
 }
 
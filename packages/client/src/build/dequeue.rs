@@ -1,10 +1,19 @@
 use crate as tg;
 use futures::{future, StreamExt as _, TryStreamExt as _};
+use std::collections::BTreeSet;
 use tangram_futures::stream::TryStreamExt as _;
 use tangram_http::{incoming::response::Ext as _, outgoing::request::Ext as _};
 
 #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
-pub struct Arg {}
+pub struct Arg {
+	/// The hosts the worker can run builds for. If this is not set, then a build for any host may be dequeued.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub hosts: Option<BTreeSet<String>>,
+
+	/// The worker's labels. Only builds whose labels are all in this set may be dequeued.
+	#[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+	pub labels: BTreeSet<String>,
+}
 
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 #[serde(transparent)]
@@ -1,5 +1,6 @@
 use crate as tg;
-use serde_with::serde_as;
+use serde_with::{serde_as, DurationSecondsWithFrac};
+use std::{collections::BTreeSet, time::Duration};
 use tangram_http::{incoming::response::Ext as _, outgoing::request::Ext as _};
 use time::format_description::well_known::Rfc3339;
 
@@ -13,6 +14,9 @@ pub struct Output {
 
 	pub host: String,
 
+	#[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+	pub labels: BTreeSet<String>,
+
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub log: Option<tg::blob::Id>,
 
@@ -31,8 +35,14 @@ pub struct Output {
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub outcomes_weight: Option<u64>,
 
+	#[serde(default)]
+	pub priority: i64,
+
 	pub retry: tg::build::Retry,
 
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub root: Option<tg::build::Id>,
+
 	pub status: tg::build::Status,
 
 	pub target: tg::target::Id,
@@ -43,6 +53,10 @@ pub struct Output {
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub targets_weight: Option<u64>,
 
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[serde_as(as = "Option<DurationSecondsWithFrac>")]
+	pub timeout: Option<Duration>,
+
 	#[serde_as(as = "Rfc3339")]
 	pub created_at: time::OffsetDateTime,
 
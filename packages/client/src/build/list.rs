@@ -0,0 +1,64 @@
+use crate as tg;
+use serde_with::serde_as;
+use tangram_http::{incoming::response::Ext as _, outgoing::request::Ext as _};
+use time::format_description::well_known::Rfc3339;
+
+#[serde_as]
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct Arg {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[serde_as(as = "Option<Rfc3339>")]
+	pub created_after: Option<time::OffsetDateTime>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[serde_as(as = "Option<Rfc3339>")]
+	pub created_before: Option<time::OffsetDateTime>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub host: Option<String>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub length: Option<u64>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub offset: Option<u64>,
+
+	/// Only list finished builds whose outcome has this kind.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub outcome: Option<tg::build::outcome::Kind>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub remote: Option<String>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub status: Option<tg::build::Status>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub target: Option<tg::target::Id>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(transparent)]
+pub struct Output {
+	pub data: Vec<tg::build::get::Output>,
+}
+
+impl tg::Client {
+	pub async fn list_builds(
+		&self,
+		arg: tg::build::list::Arg,
+	) -> tg::Result<tg::build::list::Output> {
+		let method = http::Method::GET;
+		let query = serde_urlencoded::to_string(&arg).unwrap();
+		let uri = format!("/builds?{query}");
+		let request = http::request::Builder::default().method(method).uri(uri);
+		let request = request.empty().unwrap();
+		let response = self.send(request).await?;
+		if !response.status().is_success() {
+			let error = response.json().await?;
+			return Err(error);
+		}
+		let output = response.json().await?;
+		Ok(output)
+	}
+}
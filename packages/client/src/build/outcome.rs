@@ -21,6 +21,23 @@ pub enum Data {
 	Succeeded(tg::value::Data),
 }
 
+#[derive(
+	Clone,
+	Copy,
+	Debug,
+	Eq,
+	Ord,
+	PartialEq,
+	PartialOrd,
+	serde_with::DeserializeFromStr,
+	serde_with::SerializeDisplay,
+)]
+pub enum Kind {
+	Canceled,
+	Failed,
+	Succeeded,
+}
+
 #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
 pub struct Arg {
 	#[serde(default, skip_serializing_if = "Option::is_none")]
@@ -59,6 +76,17 @@ impl Outcome {
 	}
 }
 
+impl Data {
+	#[must_use]
+	pub fn kind(&self) -> Kind {
+		match self {
+			Self::Canceled => Kind::Canceled,
+			Self::Failed(_) => Kind::Failed,
+			Self::Succeeded(_) => Kind::Succeeded,
+		}
+	}
+}
+
 impl tg::Build {
 	pub async fn outcome<H>(&self, handle: &H) -> tg::Result<tg::build::Outcome>
 	where
@@ -147,6 +175,29 @@ impl tg::Client {
 	}
 }
 
+impl std::fmt::Display for Kind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Canceled => write!(f, "canceled"),
+			Self::Failed => write!(f, "failed"),
+			Self::Succeeded => write!(f, "succeeded"),
+		}
+	}
+}
+
+impl std::str::FromStr for Kind {
+	type Err = tg::Error;
+
+	fn from_str(s: &str) -> tg::Result<Self, Self::Err> {
+		match s {
+			"canceled" => Ok(Self::Canceled),
+			"failed" => Ok(Self::Failed),
+			"succeeded" => Ok(Self::Succeeded),
+			kind => Err(tg::error!(%kind, "invalid value")),
+		}
+	}
+}
+
 impl TryFrom<tg::build::outcome::Data> for Outcome {
 	type Error = tg::Error;
 
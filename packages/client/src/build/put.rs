@@ -1,6 +1,9 @@
 use crate::{self as tg, util::serde::is_false};
-use serde_with::serde_as;
-use std::collections::{BTreeMap, BTreeSet};
+use serde_with::{serde_as, DurationSecondsWithFrac};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	time::Duration,
+};
 use tangram_http::{incoming::response::Ext as _, outgoing::request::Ext as _};
 use time::format_description::well_known::Rfc3339;
 
@@ -10,13 +13,22 @@ pub struct Arg {
 	pub id: tg::build::Id,
 	pub children: Vec<tg::build::Id>,
 	pub host: String,
+	#[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+	pub labels: BTreeSet<String>,
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub log: Option<tg::blob::Id>,
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub outcome: Option<tg::build::outcome::Data>,
+	#[serde(default)]
+	pub priority: i64,
 	pub retry: tg::build::Retry,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub root: Option<tg::build::Id>,
 	pub status: tg::build::Status,
 	pub target: tg::target::Id,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[serde_as(as = "Option<DurationSecondsWithFrac>")]
+	pub timeout: Option<Duration>,
 	#[serde_as(as = "Rfc3339")]
 	pub created_at: time::OffsetDateTime,
 	#[serde(default, skip_serializing_if = "Option::is_none")]
@@ -27,13 +27,14 @@ impl tg::Client {
 		let mut sender = self.connect_h1().await?;
 		let method = http::Method::POST;
 		let uri = "/lsp";
-		let request = http::request::Builder::default()
+		let mut request = http::request::Builder::default()
 			.method(method)
 			.uri(uri)
 			.header(http::header::CONNECTION, "upgrade")
 			.header(http::header::UPGRADE, "lsp")
 			.empty()
 			.unwrap();
+		self.authorize(&mut request);
 		let response = sender
 			.send_request(request)
 			.await
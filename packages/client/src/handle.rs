@@ -46,6 +46,11 @@ pub trait Handle: Clone + Unpin + Send + Sync + 'static {
 		>,
 	> + Send;
 
+	fn list_builds(
+		&self,
+		arg: tg::build::list::Arg,
+	) -> impl Future<Output = tg::Result<tg::build::list::Output>> + Send;
+
 	fn try_get_build(
 		&self,
 		id: &tg::build::Id,
@@ -232,11 +237,32 @@ pub trait Handle: Clone + Unpin + Send + Sync + 'static {
 
 	fn delete_remote(&self, name: &str) -> impl Future<Output = tg::Result<()>> + Send;
 
+	fn list_roots(
+		&self,
+		arg: tg::root::list::Arg,
+	) -> impl Future<Output = tg::Result<tg::root::list::Output>> + Send;
+
+	fn try_get_root(
+		&self,
+		name: &str,
+	) -> impl Future<Output = tg::Result<Option<tg::root::get::Output>>> + Send;
+
+	fn put_root(
+		&self,
+		name: &str,
+		arg: tg::root::put::Arg,
+	) -> impl Future<Output = tg::Result<()>> + Send;
+
+	fn delete_root(&self, name: &str) -> impl Future<Output = tg::Result<()>> + Send;
+
 	fn get_js_runtime_doc(&self) -> impl Future<Output = tg::Result<serde_json::Value>> + Send;
 
 	fn health(&self) -> impl Future<Output = tg::Result<tg::server::Health>> + Send;
 
-	fn clean(&self) -> impl Future<Output = tg::Result<()>> + Send;
+	fn clean(
+		&self,
+		arg: tg::server::clean::Arg,
+	) -> impl Future<Output = tg::Result<tg::server::clean::Output>> + Send;
 
 	fn list_tags(
 		&self,
@@ -262,5 +288,26 @@ pub trait Handle: Clone + Unpin + Send + Sync + 'static {
 		arg: tg::target::build::Arg,
 	) -> impl Future<Output = tg::Result<Option<tg::target::build::Output>>> + Send;
 
+	fn explain_target(
+		&self,
+		id: &tg::target::Id,
+		arg: tg::target::explain::Arg,
+	) -> impl Future<Output = tg::Result<tg::target::explain::Output>> + Send;
+
+	fn create_login(&self) -> impl Future<Output = tg::Result<tg::Login>> + Send;
+
+	fn try_get_login(
+		&self,
+		id: &tg::login::Id,
+		arg: tg::login::get::Arg,
+	) -> impl Future<Output = tg::Result<Option<tg::Login>>> + Send;
+
+	fn finish_login(
+		&self,
+		arg: tg::login::finish::Arg,
+	) -> impl Future<Output = tg::Result<()>> + Send;
+
 	fn get_user(&self, token: &str) -> impl Future<Output = tg::Result<Option<tg::User>>> + Send;
+
+	fn delete_token(&self, token: &str) -> impl Future<Output = tg::Result<()>> + Send;
 }
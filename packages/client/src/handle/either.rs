@@ -84,6 +84,16 @@ where
 		}
 	}
 
+	fn list_builds(
+		&self,
+		arg: tg::build::list::Arg,
+	) -> impl Future<Output = tg::Result<tg::build::list::Output>> {
+		match self {
+			Either::Left(s) => s.list_builds(arg).left_future(),
+			Either::Right(s) => s.list_builds(arg).right_future(),
+		}
+	}
+
 	fn try_get_build(
 		&self,
 		id: &tg::build::Id,
@@ -456,6 +466,44 @@ where
 		}
 	}
 
+	fn list_roots(
+		&self,
+		arg: tg::root::list::Arg,
+	) -> impl Future<Output = tg::Result<tg::root::list::Output>> {
+		match self {
+			Either::Left(s) => s.list_roots(arg).left_future(),
+			Either::Right(s) => s.list_roots(arg).right_future(),
+		}
+	}
+
+	fn try_get_root(
+		&self,
+		name: &str,
+	) -> impl Future<Output = tg::Result<Option<tg::root::get::Output>>> {
+		match self {
+			Either::Left(s) => s.try_get_root(name).left_future(),
+			Either::Right(s) => s.try_get_root(name).right_future(),
+		}
+	}
+
+	fn put_root(
+		&self,
+		name: &str,
+		arg: tg::root::put::Arg,
+	) -> impl Future<Output = tg::Result<()>> {
+		match self {
+			Either::Left(s) => s.put_root(name, arg).left_future(),
+			Either::Right(s) => s.put_root(name, arg).right_future(),
+		}
+	}
+
+	fn delete_root(&self, name: &str) -> impl Future<Output = tg::Result<()>> {
+		match self {
+			Either::Left(s) => s.delete_root(name).left_future(),
+			Either::Right(s) => s.delete_root(name).right_future(),
+		}
+	}
+
 	fn get_js_runtime_doc(&self) -> impl Future<Output = tg::Result<serde_json::Value>> {
 		match self {
 			Either::Left(s) => s.get_js_runtime_doc().left_future(),
@@ -470,10 +518,13 @@ where
 		}
 	}
 
-	fn clean(&self) -> impl Future<Output = tg::Result<()>> {
+	fn clean(
+		&self,
+		arg: tg::server::clean::Arg,
+	) -> impl Future<Output = tg::Result<tg::server::clean::Output>> {
 		match self {
-			Either::Left(s) => s.clean().left_future(),
-			Either::Right(s) => s.clean().right_future(),
+			Either::Left(s) => s.clean(arg).left_future(),
+			Either::Right(s) => s.clean(arg).right_future(),
 		}
 	}
 
@@ -526,10 +577,53 @@ where
 		}
 	}
 
+	fn explain_target(
+		&self,
+		id: &tg::target::Id,
+		arg: tg::target::explain::Arg,
+	) -> impl Future<Output = tg::Result<tg::target::explain::Output>> {
+		match self {
+			Either::Left(s) => s.explain_target(id, arg).left_future(),
+			Either::Right(s) => s.explain_target(id, arg).right_future(),
+		}
+	}
+
+	fn create_login(&self) -> impl Future<Output = tg::Result<tg::Login>> {
+		match self {
+			Either::Left(s) => s.create_login().left_future(),
+			Either::Right(s) => s.create_login().right_future(),
+		}
+	}
+
+	fn try_get_login(
+		&self,
+		id: &tg::login::Id,
+		arg: tg::login::get::Arg,
+	) -> impl Future<Output = tg::Result<Option<tg::Login>>> {
+		match self {
+			Either::Left(s) => s.try_get_login(id, arg).left_future(),
+			Either::Right(s) => s.try_get_login(id, arg).right_future(),
+		}
+	}
+
+	fn finish_login(&self, arg: tg::login::finish::Arg) -> impl Future<Output = tg::Result<()>> {
+		match self {
+			Either::Left(s) => s.finish_login(arg).left_future(),
+			Either::Right(s) => s.finish_login(arg).right_future(),
+		}
+	}
+
 	fn get_user(&self, token: &str) -> impl Future<Output = tg::Result<Option<tg::User>>> {
 		match self {
 			Either::Left(s) => s.get_user(token).left_future(),
 			Either::Right(s) => s.get_user(token).right_future(),
 		}
 	}
+
+	fn delete_token(&self, token: &str) -> impl Future<Output = tg::Result<()>> {
+		match self {
+			Either::Left(s) => s.delete_token(token).left_future(),
+			Either::Right(s) => s.delete_token(token).right_future(),
+		}
+	}
 }
@@ -34,6 +34,7 @@ pub enum Kind {
 	Build,
 	User,
 	Token,
+	Login,
 	Request,
 }
 
@@ -157,6 +158,7 @@ impl std::fmt::Display for Kind {
 			Self::Build => "bld",
 			Self::User => "usr",
 			Self::Token => "tok",
+			Self::Login => "lgn",
 			Self::Request => "req",
 		};
 		write!(f, "{kind}")?;
@@ -179,6 +181,7 @@ impl std::str::FromStr for Kind {
 			"bld" | "build" => Self::Build,
 			"usr" | "user" => Self::User,
 			"tok" | "token" => Self::Token,
+			"lgn" | "login" => Self::Login,
 			"req" | "request" => Self::Request,
 			_ => {
 				return Err(tg::error!(%s, "invalid kind"));
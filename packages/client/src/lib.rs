@@ -32,6 +32,7 @@ pub use self::{
 	leaf::Leaf,
 	location::Location,
 	lockfile::Lockfile,
+	login::Login,
 	module::Module,
 	mutation::Mutation,
 	object::Handle as Object,
@@ -46,6 +47,9 @@ pub use self::{
 	value::Value,
 };
 
+#[cfg(feature = "tls")]
+pub use self::tls::Tls;
+
 pub mod artifact;
 pub mod blob;
 pub mod branch;
@@ -63,6 +67,7 @@ pub mod import;
 pub mod leaf;
 pub mod location;
 pub mod lockfile;
+pub mod login;
 pub mod module;
 pub mod mutation;
 pub mod object;
@@ -73,12 +78,15 @@ pub mod progress;
 pub mod range;
 pub mod reference;
 pub mod remote;
+pub mod root;
 pub mod runtime;
 pub mod server;
 pub mod symlink;
 pub mod tag;
 pub mod target;
 pub mod template;
+#[cfg(feature = "tls")]
+pub mod tls;
 pub mod user;
 pub mod util;
 pub mod value;
@@ -89,14 +97,42 @@ pub struct Client(Arc<Inner>);
 #[derive(Debug)]
 pub struct Inner {
 	url: Url,
+	token: Option<String>,
+	#[cfg(feature = "tls")]
+	tls: Option<Tls>,
 	sender: tokio::sync::Mutex<Option<hyper::client::conn::http2::SendRequest<Outgoing>>>,
 }
 
 impl Client {
 	#[must_use]
 	pub fn new(url: Url) -> Self {
+		Self::with_token(url, None)
+	}
+
+	/// Create a client that authenticates every request with a bearer token.
+	#[must_use]
+	pub fn with_token(url: Url, token: Option<String>) -> Self {
+		let sender = tokio::sync::Mutex::new(None);
+		Self(Arc::new(Inner {
+			url,
+			token,
+			#[cfg(feature = "tls")]
+			tls: None,
+			sender,
+		}))
+	}
+
+	/// Create a client that authenticates every request with a bearer token and connects with the TLS options.
+	#[cfg(feature = "tls")]
+	#[must_use]
+	pub fn with_tls(url: Url, token: Option<String>, tls: Option<Tls>) -> Self {
 		let sender = tokio::sync::Mutex::new(None);
-		Self(Arc::new(Inner { url, sender }))
+		Self(Arc::new(Inner {
+			url,
+			token,
+			tls,
+			sender,
+		}))
 	}
 
 	pub fn with_env() -> tg::Result<Self> {
@@ -114,7 +150,8 @@ impl Client {
 					"could not parse a URL from the TANGRAM_URL environment variable"
 				)
 			})?;
-		Ok(Self::new(url))
+		let token = std::env::var("TANGRAM_TOKEN").ok();
+		Ok(Self::with_token(url, token))
 	}
 
 	#[must_use]
@@ -122,6 +159,25 @@ impl Client {
 		&self.url
 	}
 
+	#[must_use]
+	pub fn token(&self) -> Option<&str> {
+		self.token.as_deref()
+	}
+
+	/// Add the client's token to a request, unless the request already has an authorization header.
+	fn authorize(&self, request: &mut http::Request<Outgoing>) {
+		let Some(token) = &self.token else {
+			return;
+		};
+		let Ok(value) = http::HeaderValue::from_str(&format!("Bearer {token}")) else {
+			return;
+		};
+		request
+			.headers_mut()
+			.entry(http::header::AUTHORIZATION)
+			.or_insert(value);
+	}
+
 	pub async fn connect(&self) -> tg::Result<()> {
 		self.sender().boxed().await.map(|_| ())
 	}
@@ -482,16 +538,40 @@ impl Client {
 			.await
 			.map_err(|source| tg::error!(!source, "failed to create the TCP connection"))?;
 
-		// Create the connector.
+		// Create the root certificate store.
+		let tls = self.tls.clone().unwrap_or_default();
 		let mut root_store = rustls::RootCertStore::empty();
 		root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
-		let mut config = rustls::ClientConfig::builder_with_provider(Arc::new(
+		if let Some(path) = &tls.certificate_authorities {
+			for certificate in tg::tls::read_certificates(path)? {
+				root_store.add(certificate).map_err(|source| {
+					tg::error!(!source, "failed to add the certificate authority")
+				})?;
+			}
+		}
+
+		// Create the connector.
+		let builder = rustls::ClientConfig::builder_with_provider(Arc::new(
 			rustls::crypto::aws_lc_rs::default_provider(),
 		))
 		.with_safe_default_protocol_versions()
 		.unwrap()
-		.with_root_certificates(root_store)
-		.with_no_client_auth();
+		.with_root_certificates(root_store);
+		let mut config = match (&tls.certificate, &tls.key) {
+			(Some(certificate), Some(path)) => {
+				let certificates = tg::tls::read_certificates(certificate)?;
+				let key = tg::tls::read_key(path)?;
+				builder
+					.with_client_auth_cert(certificates, key)
+					.map_err(|source| tg::error!(!source, "invalid certificate or key"))?
+			},
+			(None, None) => builder.with_no_client_auth(),
+			_ => {
+				return Err(tg::error!(
+					"expected both a client certificate and a key to be set"
+				));
+			},
+		};
 		config.alpn_protocols = protocols;
 		let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
 
@@ -509,7 +589,11 @@ impl Client {
 		Ok(stream)
 	}
 
-	async fn send(&self, request: http::Request<Outgoing>) -> tg::Result<http::Response<Incoming>> {
+	async fn send(
+		&self,
+		mut request: http::Request<Outgoing>,
+	) -> tg::Result<http::Response<Incoming>> {
+		self.authorize(&mut request);
 		if request.body().try_clone().is_some() {
 			self.send_with_retry(request).await
 		} else {
@@ -606,6 +690,13 @@ impl tg::Handle for Client {
 		self.try_read_blob_stream(id, arg)
 	}
 
+	fn list_builds(
+		&self,
+		arg: tg::build::list::Arg,
+	) -> impl Future<Output = tg::Result<tg::build::list::Output>> {
+		self.list_builds(arg)
+	}
+
 	fn try_get_build(
 		&self,
 		id: &tg::build::Id,
@@ -846,6 +937,32 @@ impl tg::Handle for Client {
 		self.delete_remote(name)
 	}
 
+	fn list_roots(
+		&self,
+		arg: tg::root::list::Arg,
+	) -> impl Future<Output = tg::Result<tg::root::list::Output>> {
+		self.list_roots(arg)
+	}
+
+	fn try_get_root(
+		&self,
+		name: &str,
+	) -> impl Future<Output = tg::Result<Option<tg::root::get::Output>>> {
+		self.try_get_root(name)
+	}
+
+	fn put_root(
+		&self,
+		name: &str,
+		arg: tg::root::put::Arg,
+	) -> impl Future<Output = tg::Result<()>> {
+		self.put_root(name, arg)
+	}
+
+	fn delete_root(&self, name: &str) -> impl Future<Output = tg::Result<()>> {
+		self.delete_root(name)
+	}
+
 	fn get_js_runtime_doc(&self) -> impl Future<Output = tg::Result<serde_json::Value>> {
 		self.get_js_runtime_doc()
 	}
@@ -854,8 +971,11 @@ impl tg::Handle for Client {
 		self.health()
 	}
 
-	fn clean(&self) -> impl Future<Output = tg::Result<()>> {
-		self.clean()
+	fn clean(
+		&self,
+		arg: tg::server::clean::Arg,
+	) -> impl Future<Output = tg::Result<tg::server::clean::Output>> {
+		self.clean(arg)
 	}
 
 	fn list_tags(
@@ -892,9 +1012,37 @@ impl tg::Handle for Client {
 		self.try_build_target(id, arg)
 	}
 
+	fn explain_target(
+		&self,
+		id: &tg::target::Id,
+		arg: tg::target::explain::Arg,
+	) -> impl Future<Output = tg::Result<tg::target::explain::Output>> {
+		self.explain_target(id, arg)
+	}
+
+	fn create_login(&self) -> impl Future<Output = tg::Result<tg::Login>> {
+		self.create_login()
+	}
+
+	fn try_get_login(
+		&self,
+		id: &tg::login::Id,
+		arg: tg::login::get::Arg,
+	) -> impl Future<Output = tg::Result<Option<tg::Login>>> {
+		self.try_get_login(id, arg)
+	}
+
+	fn finish_login(&self, arg: tg::login::finish::Arg) -> impl Future<Output = tg::Result<()>> {
+		self.finish_login(arg)
+	}
+
 	fn get_user(&self, token: &str) -> impl Future<Output = tg::Result<Option<tg::User>>> {
 		self.get_user(token)
 	}
+
+	fn delete_token(&self, token: &str) -> impl Future<Output = tg::Result<()>> {
+		self.delete_token(token)
+	}
 }
 
 impl std::ops::Deref for Client {
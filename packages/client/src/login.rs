@@ -0,0 +1,87 @@
+use crate as tg;
+use tangram_http::{incoming::response::Ext as _, outgoing::request::Ext as _};
+use url::Url;
+
+pub mod finish;
+pub mod get;
+
+#[derive(
+	Clone,
+	Debug,
+	Eq,
+	Hash,
+	Ord,
+	PartialEq,
+	PartialOrd,
+	derive_more::Display,
+	serde::Deserialize,
+	serde::Serialize,
+)]
+#[serde(into = "crate::Id", try_from = "crate::Id")]
+pub struct Id(crate::Id);
+
+/// A login. The user visits the login's URL to authenticate with the provider, after which either the login's token or its error is set. A finished login is only returned once, and only to the holder of its secret.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Login {
+	pub id: Id,
+	pub url: Url,
+	/// The secret that is required to get the login. It is only returned when the login is created, and unlike the login's ID, it is not part of the URL.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub secret: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub token: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub error: Option<tg::Error>,
+}
+
+impl Id {
+	#[allow(clippy::new_without_default)]
+	#[must_use]
+	pub fn new() -> Self {
+		Self(crate::Id::new_uuidv7(tg::id::Kind::Login))
+	}
+}
+
+impl tg::Client {
+	pub async fn create_login(&self) -> tg::Result<tg::Login> {
+		let method = http::Method::POST;
+		let uri = "/login";
+		let request = http::request::Builder::default()
+			.method(method)
+			.uri(uri)
+			.empty()
+			.unwrap();
+		let response = self.send(request).await?;
+		if !response.status().is_success() {
+			let error = response.json().await?;
+			return Err(error);
+		}
+		let output = response.json().await?;
+		Ok(output)
+	}
+}
+
+impl From<Id> for tg::Id {
+	fn from(value: Id) -> Self {
+		value.0
+	}
+}
+
+impl TryFrom<tg::Id> for Id {
+	type Error = tg::Error;
+
+	fn try_from(value: tg::Id) -> tg::Result<Self, Self::Error> {
+		if value.kind() != tg::id::Kind::Login {
+			return Err(tg::error!(%value, "invalid kind"));
+		}
+		Ok(Self(value))
+	}
+}
+
+impl std::str::FromStr for Id {
+	type Err = tg::Error;
+
+	fn from_str(s: &str) -> tg::Result<Self, Self::Err> {
+		tg::Id::from_str(s)?.try_into()
+	}
+}
@@ -0,0 +1,31 @@
+use crate as tg;
+use tangram_http::{incoming::response::Ext as _, outgoing::request::Ext as _};
+
+/// The query parameters of the provider's redirect back to the server.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Arg {
+	/// The authorization code issued by the provider.
+	pub code: String,
+
+	/// The ID of the login.
+	pub state: tg::login::Id,
+}
+
+impl tg::Client {
+	pub async fn finish_login(&self, arg: tg::login::finish::Arg) -> tg::Result<()> {
+		let method = http::Method::GET;
+		let query = serde_urlencoded::to_string(&arg).unwrap();
+		let uri = format!("/oauth/github?{query}");
+		let request = http::request::Builder::default()
+			.method(method)
+			.uri(uri)
+			.empty()
+			.unwrap();
+		let response = self.send(request).await?;
+		if !response.status().is_success() {
+			let error = response.json().await?;
+			return Err(error);
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,35 @@
+use crate as tg;
+use tangram_http::{incoming::response::Ext as _, outgoing::request::Ext as _};
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Arg {
+	/// The login's secret, which is returned when the login is created.
+	pub secret: String,
+}
+
+impl tg::Client {
+	pub async fn try_get_login(
+		&self,
+		id: &tg::login::Id,
+		arg: tg::login::get::Arg,
+	) -> tg::Result<Option<tg::Login>> {
+		let method = http::Method::GET;
+		let query = serde_urlencoded::to_string(&arg).unwrap();
+		let uri = format!("/login/{id}?{query}");
+		let request = http::request::Builder::default()
+			.method(method)
+			.uri(uri)
+			.empty()
+			.unwrap();
+		let response = self.send(request).await?;
+		if response.status() == http::StatusCode::NOT_FOUND {
+			return Ok(None);
+		}
+		if !response.status().is_success() {
+			let error = response.json().await?;
+			return Err(error);
+		}
+		let output = response.json().await?;
+		Ok(Some(output))
+	}
+}
@@ -2,3 +2,23 @@ pub mod delete;
 pub mod get;
 pub mod list;
 pub mod put;
+
+/// Where a remote is configured.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+	/// The remote is configured in the server's options.
+	Config,
+
+	/// The remote was added with `put_remote` and is stored in the server's database. These take precedence over remotes in the server's options.
+	Database,
+}
+
+impl std::fmt::Display for Source {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Config => write!(f, "config"),
+			Self::Database => write!(f, "database"),
+		}
+	}
+}
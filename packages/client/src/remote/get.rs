@@ -5,6 +5,10 @@ use url::Url;
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub struct Output {
 	pub name: String,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub source: Option<tg::remote::Source>,
+
 	pub url: Url,
 }
 
@@ -0,0 +1,4 @@
+pub mod delete;
+pub mod get;
+pub mod list;
+pub mod put;
@@ -0,0 +1,27 @@
+use crate as tg;
+use tangram_either::Either;
+use tangram_http::{incoming::response::Ext as _, outgoing::request::Ext as _};
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Arg {
+	pub item: Either<tg::build::Id, tg::object::Id>,
+}
+
+impl tg::Client {
+	pub async fn put_root(&self, name: &str, arg: tg::root::put::Arg) -> tg::Result<()> {
+		let method = http::Method::PUT;
+		let name = urlencoding::encode(name);
+		let uri = format!("/roots/{name}");
+		let request = http::request::Builder::default()
+			.method(method)
+			.uri(uri)
+			.json(arg)
+			.unwrap();
+		let response = self.send(request).await?;
+		if !response.status().is_success() {
+			let error = response.json().await?;
+			return Err(error);
+		}
+		Ok(())
+	}
+}
@@ -1,20 +1,51 @@
-use crate as tg;
+use crate::{self as tg, util::serde::is_false};
+use serde_with::{serde_as, DurationSecondsWithFrac};
+use std::time::Duration;
 use tangram_http::{incoming::response::Ext as _, outgoing::request::Ext as _};
 
+#[serde_as]
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct Arg {
+	/// If this flag is set, then nothing will be removed, and the output will describe what would have been removed.
+	#[serde(default, skip_serializing_if = "is_false")]
+	pub dry_run: bool,
+
+	/// Builds and objects touched more recently than this are kept, even if they are not reachable from a root.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[serde_as(as = "Option<DurationSecondsWithFrac>")]
+	pub max_age: Option<Duration>,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct Output {
+	/// The number of builds that were removed.
+	pub builds: u64,
+
+	/// The number of objects that were removed.
+	pub objects: u64,
+
+	/// The number of bytes that were reclaimed.
+	pub bytes: u64,
+}
+
 impl tg::Client {
-	pub async fn clean(&self) -> tg::Result<()> {
+	pub async fn clean(
+		&self,
+		arg: tg::server::clean::Arg,
+	) -> tg::Result<tg::server::clean::Output> {
 		let method = http::Method::POST;
 		let uri = "/clean";
 		let request = http::request::Builder::default()
 			.method(method)
 			.uri(uri)
-			.empty()
+			.json(arg)
 			.unwrap();
 		let response = self.send(request).await?;
 		if !response.status().is_success() {
 			let error = response.json().await?;
 			return Err(error);
 		}
-		Ok(())
+		let output = response.json().await?;
+		Ok(output)
 	}
 }
@@ -11,6 +11,7 @@ use std::{
 };
 
 pub mod build;
+pub mod explain;
 
 #[derive(
 	Clone,
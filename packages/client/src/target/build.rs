@@ -0,0 +1,119 @@
+use crate::{
+	self as tg,
+	handle::Ext as _,
+	util::serde::{is_true, return_true},
+};
+use serde_with::{serde_as, DurationSecondsWithFrac};
+use std::{collections::BTreeSet, time::Duration};
+use tangram_http::{incoming::response::Ext as _, outgoing::request::Ext as _};
+
+#[serde_as]
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Arg {
+	#[serde(default = "return_true", skip_serializing_if = "is_true")]
+	pub create: bool,
+
+	#[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+	pub labels: BTreeSet<String>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub parent: Option<tg::build::Id>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub priority: Option<i64>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub remote: Option<String>,
+
+	#[serde(default, skip_serializing_if = "retry_is_canceled")]
+	pub retry: tg::build::Retry,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[serde_as(as = "Option<DurationSecondsWithFrac>")]
+	pub timeout: Option<Duration>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(transparent)]
+pub struct Output {
+	pub build: tg::build::Id,
+}
+
+impl tg::Target {
+	pub async fn build<H>(&self, handle: &H, arg: tg::target::build::Arg) -> tg::Result<tg::Build>
+	where
+		H: tg::Handle,
+	{
+		let id = self.id(handle).await?;
+		let output = handle.build_target(&id, arg).await?;
+		let build = tg::Build::with_id(output.build);
+		Ok(build)
+	}
+
+	pub async fn outcome<H>(
+		&self,
+		handle: &H,
+		arg: tg::target::build::Arg,
+	) -> tg::Result<tg::build::Outcome>
+	where
+		H: tg::Handle,
+	{
+		let build = self.build(handle, arg).await?;
+		let outcome = build.outcome(handle).await?;
+		Ok(outcome)
+	}
+
+	pub async fn output<H>(&self, handle: &H, arg: tg::target::build::Arg) -> tg::Result<tg::Value>
+	where
+		H: tg::Handle,
+	{
+		let build = self.build(handle, arg).await?;
+		let output = build.output(handle).await?;
+		Ok(output)
+	}
+}
+
+impl tg::Client {
+	pub async fn try_build_target(
+		&self,
+		id: &tg::target::Id,
+		arg: tg::target::build::Arg,
+	) -> tg::Result<Option<tg::target::build::Output>> {
+		let method = http::Method::POST;
+		let uri = format!("/targets/{id}/build");
+		let request = http::request::Builder::default()
+			.method(method)
+			.uri(uri)
+			.json(arg)
+			.unwrap();
+		let response = self.send(request).await?;
+		if response.status() == http::StatusCode::NOT_FOUND {
+			return Ok(None);
+		}
+		if !response.status().is_success() {
+			let error = response.json().await?;
+			return Err(error);
+		}
+		let output = response.json().await?;
+		Ok(output)
+	}
+}
+
+impl Default for Arg {
+	fn default() -> Self {
+		Self {
+			create: true,
+			labels: BTreeSet::new(),
+			parent: None,
+			priority: None,
+			remote: None,
+			retry: tg::build::Retry::default(),
+			timeout: None,
+		}
+	}
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn retry_is_canceled(retry: &tg::build::Retry) -> bool {
+	matches!(retry, tg::build::Retry::Canceled)
+}
@@ -0,0 +1,57 @@
+use crate as tg;
+use tangram_http::{incoming::response::Ext as _, outgoing::request::Ext as _};
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct Arg {
+	#[serde(default)]
+	pub retry: tg::build::Retry,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Output {
+	/// The target's local builds, most recently created first.
+	pub builds: Vec<tg::build::get::Output>,
+
+	/// The decision that building the target with the retry would make.
+	pub decision: Decision,
+
+	/// Why the decision was made.
+	pub reason: String,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Decision {
+	/// An existing local build would be used.
+	Local { build: tg::build::Id },
+
+	/// An existing build on a remote would be used.
+	Remote {
+		build: tg::build::Id,
+		remote: String,
+	},
+
+	/// A new build would be created.
+	New,
+}
+
+impl tg::Client {
+	pub async fn explain_target(
+		&self,
+		id: &tg::target::Id,
+		arg: tg::target::explain::Arg,
+	) -> tg::Result<tg::target::explain::Output> {
+		let method = http::Method::GET;
+		let query = serde_urlencoded::to_string(&arg).unwrap();
+		let uri = format!("/targets/{id}/explain?{query}");
+		let request = http::request::Builder::default().method(method).uri(uri);
+		let request = request.empty().unwrap();
+		let response = self.send(request).await?;
+		if !response.status().is_success() {
+			let error = response.json().await?;
+			return Err(error);
+		}
+		let output = response.json().await?;
+		Ok(output)
+	}
+}
@@ -0,0 +1,43 @@
+use crate as tg;
+use std::path::{Path, PathBuf};
+
+/// The TLS options for a client's connections to an `https` URL.
+#[derive(Clone, Debug, Default)]
+pub struct Tls {
+	/// The path to a PEM file of certificate authorities to trust in addition to the web PKI roots.
+	pub certificate_authorities: Option<PathBuf>,
+
+	/// The path to the client's certificate chain in PEM format, which is presented to servers that request a client certificate.
+	pub certificate: Option<PathBuf>,
+
+	/// The path to the client's private key in PEM format.
+	pub key: Option<PathBuf>,
+}
+
+/// Read a chain of certificates from a PEM file.
+pub fn read_certificates(
+	path: &Path,
+) -> tg::Result<Vec<rustls::pki_types::CertificateDer<'static>>> {
+	let file = std::fs::File::open(path).map_err(
+		|source| tg::error!(!source, %path = path.display(), "failed to open the certificates"),
+	)?;
+	let certificates = rustls_pemfile::certs(&mut std::io::BufReader::new(file))
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(
+			|source| tg::error!(!source, %path = path.display(), "failed to read the certificates"),
+		)?;
+	if certificates.is_empty() {
+		return Err(tg::error!(%path = path.display(), "expected a certificate"));
+	}
+	Ok(certificates)
+}
+
+/// Read a private key from a PEM file.
+pub fn read_key(path: &Path) -> tg::Result<rustls::pki_types::PrivateKeyDer<'static>> {
+	let file = std::fs::File::open(path)
+		.map_err(|source| tg::error!(!source, %path = path.display(), "failed to open the key"))?;
+	let key = rustls_pemfile::private_key(&mut std::io::BufReader::new(file))
+		.map_err(|source| tg::error!(!source, %path = path.display(), "failed to read the key"))?
+		.ok_or_else(|| tg::error!(%path = path.display(), "expected a key"))?;
+	Ok(key)
+}
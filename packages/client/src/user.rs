@@ -1,4 +1,5 @@
 use crate as tg;
+use std::collections::BTreeSet;
 use tangram_http::{incoming::response::Ext as _, outgoing::request::Ext as _};
 
 #[derive(
@@ -20,6 +21,28 @@ pub struct Id(crate::Id);
 pub struct User {
 	pub id: Id,
 	pub email: String,
+	#[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+	pub scopes: BTreeSet<Scope>,
+}
+
+/// A permission granted to a token. The admin scope implies all other scopes.
+#[derive(
+	Clone,
+	Copy,
+	Debug,
+	Eq,
+	Hash,
+	Ord,
+	PartialEq,
+	PartialOrd,
+	serde_with::DeserializeFromStr,
+	serde_with::SerializeDisplay,
+)]
+pub enum Scope {
+	Read,
+	Write,
+	Build,
+	Admin,
 }
 
 impl tg::Client {
@@ -40,6 +63,38 @@ impl tg::Client {
 		let output = response.json().await?;
 		Ok(output)
 	}
+
+	pub async fn delete_token(&self, token: &str) -> tg::Result<()> {
+		let method = http::Method::DELETE;
+		let uri = "/user/token";
+		let request = http::request::Builder::default()
+			.method(method)
+			.uri(uri)
+			.header(http::header::AUTHORIZATION, format!("Bearer {token}"))
+			.empty()
+			.unwrap();
+		let response = self.send(request).await?;
+		if !response.status().is_success() {
+			let error = response.json().await?;
+			return Err(error);
+		}
+		Ok(())
+	}
+}
+
+impl Id {
+	#[allow(clippy::new_without_default)]
+	#[must_use]
+	pub fn new() -> Self {
+		Self(crate::Id::new_uuidv7(tg::id::Kind::User))
+	}
+}
+
+impl User {
+	#[must_use]
+	pub fn has_scope(&self, scope: Scope) -> bool {
+		self.scopes.contains(&Scope::Admin) || self.scopes.contains(&scope)
+	}
 }
 
 impl From<Id> for tg::Id {
@@ -66,3 +121,28 @@ impl std::str::FromStr for Id {
 		tg::Id::from_str(s)?.try_into()
 	}
 }
+
+impl std::fmt::Display for Scope {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Read => write!(f, "read"),
+			Self::Write => write!(f, "write"),
+			Self::Build => write!(f, "build"),
+			Self::Admin => write!(f, "admin"),
+		}
+	}
+}
+
+impl std::str::FromStr for Scope {
+	type Err = tg::Error;
+
+	fn from_str(s: &str) -> tg::Result<Self, Self::Err> {
+		match s {
+			"read" => Ok(Self::Read),
+			"write" => Ok(Self::Write),
+			"build" => Ok(Self::Build),
+			"admin" => Ok(Self::Admin),
+			scope => Err(tg::error!(%scope, "invalid value")),
+		}
+	}
+}
@@ -129,6 +129,7 @@ fn id_kind(input: &mut Input) -> PResult<tg::id::Kind> {
 		alt(("bld", "build")).value(tg::id::Kind::Build),
 		alt(("usr", "user")).value(tg::id::Kind::User),
 		alt(("tok", "token")).value(tg::id::Kind::Token),
+		alt(("lgn", "login")).value(tg::id::Kind::Login),
 		alt(("req", "request")).value(tg::id::Kind::Request),
 	))
 	.parse_next(input)
@@ -54,12 +54,13 @@ export namespace Artifact {
 
 	export let extract = async (
 		blob: tg.Blob,
-		format: ArchiveFormat,
+		format?: ArchiveFormat,
 	): Promise<Artifact> => {
 		let value = await (
 			await tg.target({
 				host: "builtin",
-				args: ["extract", blob, format],
+				args:
+					format !== undefined ? ["extract", blob, format] : ["extract", blob],
 				env: undefined,
 			})
 		).output();
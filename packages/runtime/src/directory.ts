@@ -277,4 +277,40 @@ export namespace Directory {
 		| { graph: tg.Graph; node: number };
 
 	export type State = tg.Object.State<Directory.Id, Directory.Object>;
+
+	export type ImageConfig = {
+		architecture: string;
+		cmd?: Array<string> | undefined;
+		compression?: "gz" | "zstd" | undefined;
+		entrypoint?: Array<string> | undefined;
+		env?: { [key: string]: string } | undefined;
+		user?: string | undefined;
+		workingDir?: string | undefined;
+	};
+
+	export let exportImage = async (
+		directory: Directory,
+		config: ImageConfig,
+	): Promise<tg.Blob> => {
+		let value = await (
+			await tg.target({
+				host: "builtin",
+				args: ["export_image", directory, config],
+				env: undefined,
+			})
+		).output();
+		tg.assert(tg.Blob.is(value));
+		return value;
+	};
+
+	export let importImage = async (blob: tg.Blob): Promise<Directory> => {
+		let value = await (
+			await tg.target({
+				host: "builtin",
+				args: ["import_image", blob],
+				env: undefined,
+			})
+		).output();
+		return Directory.expect(value);
+	};
 }
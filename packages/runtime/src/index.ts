@@ -36,7 +36,9 @@ let bundle = Artifact.bundle;
 let compress = Blob.compress;
 let decompress = Blob.decompress;
 let download = Blob.download;
+let exportImage = Directory.exportImage;
 let extract = Artifact.extract;
+let importImage = Directory.importImage;
 
 export {
 	Args,
@@ -68,9 +70,11 @@ export {
 	directory,
 	download,
 	encoding,
+	exportImage,
 	extract,
 	file,
 	graph,
+	importImage,
 	leaf,
 	log,
 	mutation,
@@ -233,7 +233,7 @@ declare namespace tg {
 	/** Extract an artifact from an archive. **/
 	export let extract: (
 		blob: tg.Blob,
-		format: tg.Artifact.ArchiveFormat,
+		format?: tg.Artifact.ArchiveFormat,
 	) => Promise<tg.Artifact>;
 
 	/** Bundle an artifact. **/
@@ -266,7 +266,7 @@ declare namespace tg {
 		/** Extract an artifact from an archive. **/
 		export let extract: (
 			blob: tg.Blob,
-			format: tg.Artifact.ArchiveFormat,
+			format?: tg.Artifact.ArchiveFormat,
 		) => Promise<tg.Artifact>;
 
 		/** Bundle an artifact. **/
@@ -279,6 +279,15 @@ declare namespace tg {
 		) => Promise<tg.Checksum>;
 	}
 
+	/** Export a directory as an OCI image layout. **/
+	export let exportImage: (
+		directory: tg.Directory,
+		config: tg.Directory.ImageConfig,
+	) => Promise<tg.Blob>;
+
+	/** Import an OCI image or a `docker save` tarball as a directory. **/
+	export let importImage: (blob: tg.Blob) => Promise<tg.Directory>;
+
 	/** Create a directory. */
 	export let directory: (
 		...args: Array<tg.Unresolved<tg.MaybeNestedArray<tg.Directory.Arg>>>
@@ -333,6 +342,30 @@ declare namespace tg {
 				| tg.Artifact
 				| ArgObject;
 		};
+
+		/** The config of an image exported from a directory. */
+		export type ImageConfig = {
+			/** The image's architecture, such as `"amd64"` or `"arm64"`. */
+			architecture: string;
+
+			/** The default arguments to the entrypoint. */
+			cmd?: Array<string> | undefined;
+
+			/** The compression format of the image's layer. The default is `"gz"`. */
+			compression?: "gz" | "zstd" | undefined;
+
+			/** The command to run when the container starts. */
+			entrypoint?: Array<string> | undefined;
+
+			/** The environment variables. */
+			env?: { [key: string]: string } | undefined;
+
+			/** The user to run as. */
+			user?: string | undefined;
+
+			/** The working directory. */
+			workingDir?: string | undefined;
+		};
 	}
 
 	/** Create a file. */
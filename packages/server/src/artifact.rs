@@ -1,2 +1,3 @@
 mod checkin;
 mod checkout;
+mod checksum;
@@ -1,8 +1,13 @@
 use super::input;
 use crate::Server;
-use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+use std::{
+	collections::BTreeMap,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
 use tangram_client as tg;
 use tangram_either::Either;
+use tg::path::Ext as _;
 
 #[derive(Clone, Debug)]
 struct State {
@@ -180,7 +185,17 @@ impl Server {
 					let id = get_reference_from_pattern(pattern);
 					outgoing.insert(edge.reference.clone(), Either::Left(id));
 				},
-				tg::reference::Path::Path(_) => return Err(tg::error!("unimplemented")),
+				tg::reference::Path::Path(path) => {
+					let id = self
+						.create_unification_node_from_path(
+							graph,
+							input.clone(),
+							path,
+							visited_graph_nodes,
+						)
+						.await?;
+					outgoing.insert(edge.reference.clone(), id);
+				},
 			}
 		}
 
@@ -197,25 +212,92 @@ impl Server {
 		Ok(id)
 	}
 
-	#[allow(clippy::unused_async)]
+	async fn create_unification_node_from_path(
+		&self,
+		graph: &mut Graph,
+		referrer: Arc<tokio::sync::RwLock<input::Graph>>,
+		path: &Path,
+		visited_graph_nodes: &mut BTreeMap<(tg::graph::Id, usize), Id>,
+	) -> tg::Result<Id> {
+		// Resolve the path relative to the referrer's parent directory.
+		let referrer_path = referrer.read().await.arg.path.clone();
+		let path = referrer_path
+			.parent()
+			.ok_or_else(
+				|| tg::error!(%path = referrer_path.display(), "expected the referrer to have a parent"),
+			)?
+			.join(path)
+			.normalize();
+
+		// If the path is already in the graph, then use its node.
+		if let Some(id) = graph.paths.get(&path).cloned() {
+			return Ok(id);
+		}
+
+		// Otherwise, find the path's input and add it to the graph.
+		let input = find_input(referrer, &path).await.ok_or_else(
+			|| tg::error!(%path = path.display(), %referrer = referrer_path.display(), "failed to resolve the path dependency"),
+		)?;
+		Box::pin(self.create_unification_graph_from_input(input, graph, visited_graph_nodes)).await
+	}
+
 	async fn create_unification_node_from_object(
 		&self,
 		graph: &mut Graph,
 		object: tg::object::Id,
 	) -> tg::Result<Id> {
-		// Get an ID.
-		let id = Either::Right(graph.counter);
-		graph.counter += 1;
+		// Create the node without a tag, unifying its edges so that its path dependencies are followed.
+		let mut visited = BTreeMap::new();
+		let tag = None;
+		let unify = true;
+		self.create_unification_node_from_tagged_object_inner(
+			graph,
+			&tg::Object::with_id(object),
+			tag,
+			unify,
+			&mut visited,
+		)
+		.await
+	}
+}
 
-		// Create a node.
-		let node = Node {
-			errors: Vec::new(),
-			outgoing: BTreeMap::new(),
-			object: Either::Right(object),
-			tag: None,
-		};
-		graph.nodes.insert(id.clone(), node);
-		Ok(id)
+/// Find the input for a path by walking up from the referrer to the first ancestor that contains the path, and then down through its children.
+async fn find_input(
+	referrer: Arc<tokio::sync::RwLock<input::Graph>>,
+	path: &Path,
+) -> Option<Arc<tokio::sync::RwLock<input::Graph>>> {
+	let mut node = referrer;
+	loop {
+		let node_path = node.read().await.arg.path.clone();
+		if path.starts_with(&node_path) {
+			break;
+		}
+		let parent = node.read().await.parent.as_ref()?.upgrade()?;
+		node = parent;
+	}
+	'outer: loop {
+		let node_path = node.read().await.arg.path.clone();
+		if node_path == path {
+			return Some(node);
+		}
+		let children = node
+			.read()
+			.await
+			.edges
+			.iter()
+			.filter_map(input::Edge::node)
+			.collect::<Vec<_>>();
+		for child in children {
+			let child_path = child.read().await.arg.path.clone();
+			if child_path != node_path
+				&& child_path.starts_with(&node_path)
+				&& path.starts_with(&child_path)
+			{
+				node = child;
+				continue 'outer;
+			}
+		}
+		return None;
 	}
 }
 
@@ -696,3 +778,101 @@ impl Graph {
 		self.nodes.get_mut(node).unwrap().errors.push(error);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::TestServer;
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn path_dependencies() {
+		let server = TestServer::new().await;
+
+		// Create a package on disk whose root module imports a module by path.
+		let package = server.temp_path().join("package");
+		tokio::fs::create_dir_all(&package).await.unwrap();
+		tokio::fs::write(
+			package.join("tangram.ts"),
+			r#"import foo from "./foo.tg.ts";"#,
+		)
+		.await
+		.unwrap();
+		tokio::fs::write(package.join("foo.tg.ts"), "export default 1;")
+			.await
+			.unwrap();
+		let package = tokio::fs::canonicalize(&package).await.unwrap();
+
+		// A path dependency of an input must point to the input for the path.
+		let arg = tg::artifact::checkin::Arg {
+			destructive: false,
+			deterministic: true,
+			ignore: true,
+			locked: false,
+			path: package.clone(),
+		};
+		let input = server.create_input_graph(arg, None).await.unwrap();
+		let (graph, _) = server.create_unification_graph(input).await.unwrap();
+		let reference = "./foo.tg.ts".parse::<tg::Reference>().unwrap();
+		let dependency = graph
+			.nodes
+			.values()
+			.find_map(|node| node.outgoing.get(&reference))
+			.expect("expected an input to have the path dependency");
+		let Either::Left(input) = &graph.nodes.get(dependency).unwrap().object else {
+			panic!("expected the dependency to be an input");
+		};
+		assert_eq!(input.read().await.arg.path, package.join("foo.tg.ts"));
+
+		// A path dependency of an object must point to the dependency's object.
+		let bar = tg::File::with_contents("export default 2;");
+		let baz = tg::File::with_contents("export default 3;");
+		let pattern = "baz".parse::<tg::tag::Pattern>().unwrap();
+		let foo = tg::File::builder(r#"import bar from "./bar.tg.ts"; import baz from "baz";"#)
+			.dependencies([
+				(
+					tg::Reference::with_path("./bar.tg.ts"),
+					tg::file::Dependency {
+						object: bar.clone().into(),
+						tag: None,
+					},
+				),
+				(
+					tg::Reference::with_tag(&pattern).unwrap(),
+					tg::file::Dependency {
+						object: baz.into(),
+						tag: None,
+					},
+				),
+			])
+			.build();
+		let foo = foo.id(&*server).await.unwrap();
+		let bar = bar.id(&*server).await.unwrap();
+		let mut graph = Graph::default();
+		let root = server
+			.create_unification_node_from_object(&mut graph, foo.into())
+			.await
+			.unwrap();
+		let reference = tg::Reference::with_path("./bar.tg.ts");
+		let dependency = graph
+			.nodes
+			.get(&root)
+			.unwrap()
+			.outgoing
+			.get(&reference)
+			.expect("expected the object to have the path dependency");
+		let object = graph.nodes.get(dependency).unwrap().object.clone();
+		assert_eq!(object.right(), Some(bar.into()));
+
+		// A tag dependency of an object is left for unification instead of being walked.
+		let reference = tg::Reference::with_tag(&pattern).unwrap();
+		let dependency = graph
+			.nodes
+			.get(&root)
+			.unwrap()
+			.outgoing
+			.get(&reference)
+			.unwrap();
+		assert!(dependency.is_left());
+		assert!(!graph.nodes.contains_key(dependency));
+	}
+}
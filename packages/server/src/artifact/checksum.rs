@@ -0,0 +1,148 @@
+use crate::Server;
+use tangram_client as tg;
+
+impl Server {
+	/// Compute the checksum of an artifact.
+	///
+	/// The artifact is serialized as follows and then hashed with the algorithm. All integers are little endian `u64`s, and all strings and IDs are prefixed with their length in bytes.
+	///
+	/// - A directory is the byte `0`, the number of entries, and then for each entry in order of its name, the name followed by the entry's serialization.
+	/// - A file is the byte `1`, the byte `1` if it is executable or `0` otherwise, the length of its contents, its contents, the number of dependencies, and then for each dependency in order of its reference, the reference followed by the ID of the dependency's object.
+	/// - A symlink is the byte `2`, then either the byte `1` followed by the ID of its artifact or the byte `0`, and then either the byte `1` followed by its path or the byte `0`.
+	pub(crate) async fn checksum_artifact(
+		&self,
+		artifact: &tg::Artifact,
+		algorithm: tg::checksum::Algorithm,
+	) -> tg::Result<tg::Checksum> {
+		if let tg::checksum::Algorithm::Unsafe = algorithm {
+			return Ok(tg::Checksum::Unsafe);
+		}
+		let mut writer = tg::checksum::Writer::new(algorithm);
+		self.checksum_artifact_inner(artifact, &mut writer).await?;
+		let checksum = writer.finalize();
+		Ok(checksum)
+	}
+
+	async fn checksum_artifact_inner(
+		&self,
+		artifact: &tg::Artifact,
+		writer: &mut tg::checksum::Writer,
+	) -> tg::Result<()> {
+		match artifact {
+			tg::Artifact::Directory(directory) => {
+				let entries = directory.entries(self).await?;
+				writer.update([0]);
+				write_length(writer, entries.len());
+				for (name, artifact) in &entries {
+					write_string(writer, name);
+					Box::pin(self.checksum_artifact_inner(artifact, writer)).await?;
+				}
+			},
+
+			tg::Artifact::File(file) => {
+				let contents = file.contents(self).await?;
+				let executable = file.executable(self).await?;
+				let dependencies = file.dependencies(self).await?;
+				writer.update([1, u8::from(executable)]);
+				writer.update(contents.size(self).await?.to_le_bytes());
+				let mut reader = contents.reader(self).await?;
+				tokio::io::copy(&mut reader, writer)
+					.await
+					.map_err(|source| {
+						tg::error!(!source, "failed to copy from the reader to the writer")
+					})?;
+				write_length(writer, dependencies.len());
+				for (reference, dependency) in &dependencies {
+					write_string(writer, &reference.to_string());
+					let id = dependency.object.id(self).await?;
+					write_string(writer, &id.to_string());
+				}
+			},
+
+			tg::Artifact::Symlink(symlink) => {
+				let artifact = match symlink.artifact(self).await? {
+					Some(artifact) => Some(artifact.id(self).await?.to_string()),
+					None => None,
+				};
+				let path = symlink.path(self).await?;
+				write_symlink(writer, artifact.as_deref(), path.as_deref());
+			},
+		}
+		Ok(())
+	}
+}
+
+fn write_symlink(writer: &mut tg::checksum::Writer, artifact: Option<&str>, path: Option<&str>) {
+	writer.update([2]);
+	write_option(writer, artifact);
+	write_option(writer, path);
+}
+
+fn write_option(writer: &mut tg::checksum::Writer, string: Option<&str>) {
+	if let Some(string) = string {
+		writer.update([1]);
+		write_string(writer, string);
+	} else {
+		writer.update([0]);
+	}
+}
+
+fn write_length(writer: &mut tg::checksum::Writer, length: usize) {
+	writer.update((length as u64).to_le_bytes());
+}
+
+fn write_string(writer: &mut tg::checksum::Writer, string: &str) {
+	write_length(writer, string.len());
+	writer.update(string.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::TestServer;
+	use std::collections::BTreeMap;
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn checksum() {
+		let server = TestServer::new().await;
+
+		// Create a tree with a file, an executable, a symlink, and a nested directory.
+		let nested = tg::Directory::with_entries(BTreeMap::from([(
+			"file.txt".to_owned(),
+			tg::File::with_contents("nested").into(),
+		)]));
+		let directory = tg::Directory::with_entries(BTreeMap::from([
+			(
+				"hello.txt".to_owned(),
+				tg::File::with_contents("Hello, world!").into(),
+			),
+			(
+				"script.sh".to_owned(),
+				tg::File::builder("#!/bin/sh\n")
+					.executable(true)
+					.build()
+					.into(),
+			),
+			(
+				"link".to_owned(),
+				tg::Symlink::with_artifact_and_path(None, Some("hello.txt".to_owned())).into(),
+			),
+			("nested".to_owned(), nested.into()),
+		]));
+		let artifact = tg::Artifact::from(directory);
+
+		// The checksum must match the documented serialization.
+		let checksum = server
+			.checksum_artifact(&artifact, tg::checksum::Algorithm::Sha256)
+			.await
+			.unwrap();
+		assert_eq!(
+			checksum.to_string(),
+			"sha256:3f3c8ea04536d1f4f32604c01d1ca1ffdcfffbb58c9b165ceecb3609e636112b"
+		);
+		assert_eq!(
+			checksum.to_string().parse::<tg::Checksum>().unwrap(),
+			checksum
+		);
+	}
+}
@@ -0,0 +1,184 @@
+use crate::Server;
+use itertools::Itertools as _;
+use tangram_client as tg;
+use tangram_http::{incoming::request::Ext as _, outgoing::response::Ext as _, Incoming, Outgoing};
+
+impl Server {
+	/// Authorize a request. If the request is not authorized, then this returns the response to send instead.
+	pub(crate) async fn authorize_request<H>(
+		handle: &H,
+		request: &http::Request<Incoming>,
+		authorization: &crate::options::Authorization,
+	) -> Option<http::Response<Outgoing>>
+	where
+		H: tg::Handle,
+	{
+		// Get the scope the route requires.
+		let path_components = request.uri().path().split('/').skip(1).collect_vec();
+		let scope = Self::get_route_scope(request.method(), &path_components)?;
+
+		// Allow the request if the scope is granted to anonymous requests.
+		let anonymous = &authorization.anonymous;
+		if anonymous.contains(&tg::user::Scope::Admin) || anonymous.contains(&scope) {
+			return None;
+		}
+
+		// Get the user for the request's token.
+		let user = match request.token(None) {
+			Some(token) => match handle.get_user(token).await {
+				Ok(user) => user,
+				Err(error) => {
+					let response = http::Response::builder()
+						.status(http::StatusCode::INTERNAL_SERVER_ERROR)
+						.json(error)
+						.unwrap();
+					return Some(response);
+				},
+			},
+			None => None,
+		};
+
+		// Return an unauthorized response if there is no user.
+		let Some(user) = user else {
+			let error = tg::error!("unauthorized");
+			let response = http::Response::builder()
+				.status(http::StatusCode::UNAUTHORIZED)
+				.json(error)
+				.unwrap();
+			return Some(response);
+		};
+
+		// Return a forbidden response if the user's token does not have the scope.
+		if !user.has_scope(scope) {
+			let error = tg::error!(%scope, "forbidden");
+			let response = http::Response::builder()
+				.status(http::StatusCode::FORBIDDEN)
+				.json(error)
+				.unwrap();
+			return Some(response);
+		}
+
+		None
+	}
+
+	/// Get the scope required to access a route. Routes that are not listed here require the admin scope, including the routes that read or write paths on the server's file system, such as checking in and checking out artifacts and the language server.
+	fn get_route_scope(method: &http::Method, path: &[&str]) -> Option<tg::user::Scope> {
+		let scope = match *method {
+			http::Method::GET => match path {
+				["health" | "user"] | ["login", _] | ["oauth", "github"] => return None,
+				["remotes", ..] => tg::user::Scope::Admin,
+				_ => tg::user::Scope::Read,
+			},
+			http::Method::HEAD => tg::user::Scope::Read,
+			http::Method::POST => match path {
+				["login"] => return None,
+				["packages", "check" | "document"] => tg::user::Scope::Read,
+				["blobs"]
+				| ["builds" | "objects", _, "push" | "pull"]
+				| ["builds", _, "touch"]
+				| ["packages", "format"]
+				| ["targets", _, "build"] => tg::user::Scope::Write,
+				["builds", "dequeue"] | ["builds", _, "start" | "log" | "finish" | "heartbeat"] => {
+					tg::user::Scope::Build
+				},
+				_ => tg::user::Scope::Admin,
+			},
+			http::Method::PUT => match path {
+				["builds" | "objects" | "roots", _] | ["tags", ..] => tg::user::Scope::Write,
+				_ => tg::user::Scope::Admin,
+			},
+			http::Method::DELETE => match path {
+				["user", "token"] => return None,
+				["roots", _] | ["tags", ..] => tg::user::Scope::Write,
+				_ => tg::user::Scope::Admin,
+			},
+			_ => tg::user::Scope::Admin,
+		};
+		Some(scope)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn route_scopes() {
+		let cases = [
+			(http::Method::GET, "/health", None),
+			(http::Method::GET, "/user", None),
+			(http::Method::GET, "/login/id", None),
+			(http::Method::POST, "/login", None),
+			(http::Method::GET, "/builds/id", Some(tg::user::Scope::Read)),
+			(http::Method::GET, "/remotes", Some(tg::user::Scope::Admin)),
+			(
+				http::Method::HEAD,
+				"/objects/id",
+				Some(tg::user::Scope::Read),
+			),
+			(
+				http::Method::POST,
+				"/packages/check",
+				Some(tg::user::Scope::Read),
+			),
+			(http::Method::POST, "/blobs", Some(tg::user::Scope::Write)),
+			(
+				http::Method::POST,
+				"/targets/id/build",
+				Some(tg::user::Scope::Write),
+			),
+			(
+				http::Method::POST,
+				"/builds/dequeue",
+				Some(tg::user::Scope::Build),
+			),
+			(
+				http::Method::POST,
+				"/builds/id/finish",
+				Some(tg::user::Scope::Build),
+			),
+			(
+				http::Method::POST,
+				"/artifacts/checkin",
+				Some(tg::user::Scope::Admin),
+			),
+			(
+				http::Method::POST,
+				"/artifacts/id/checkout",
+				Some(tg::user::Scope::Admin),
+			),
+			(http::Method::POST, "/lsp", Some(tg::user::Scope::Admin)),
+			(http::Method::POST, "/clean", Some(tg::user::Scope::Admin)),
+			(http::Method::PUT, "/tags/a/b", Some(tg::user::Scope::Write)),
+			(
+				http::Method::PUT,
+				"/remotes/a",
+				Some(tg::user::Scope::Admin),
+			),
+			(http::Method::DELETE, "/user/token", None),
+			(
+				http::Method::DELETE,
+				"/roots/a",
+				Some(tg::user::Scope::Write),
+			),
+			(
+				http::Method::DELETE,
+				"/remotes/a",
+				Some(tg::user::Scope::Admin),
+			),
+			(
+				http::Method::PATCH,
+				"/builds/id",
+				Some(tg::user::Scope::Admin),
+			),
+		];
+		for (method, path, expected) in cases {
+			let path_components = path.split('/').skip(1).collect_vec();
+			assert_eq!(
+				Server::get_route_scope(&method, &path_components),
+				expected,
+				"{method} {path}"
+			);
+		}
+	}
+}
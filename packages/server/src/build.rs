@@ -9,6 +9,7 @@ mod finish;
 mod get;
 mod heartbeat;
 mod index;
+mod list;
 mod log;
 mod outcome;
 mod pull;
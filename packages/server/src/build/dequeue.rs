@@ -13,7 +13,7 @@ use tokio_stream::wrappers::IntervalStream;
 impl Server {
 	pub async fn try_dequeue_build(
 		&self,
-		_arg: tg::build::dequeue::Arg,
+		arg: tg::build::dequeue::Arg,
 	) -> tg::Result<Option<tg::build::dequeue::Output>> {
 		// Create the event stream.
 		let created = self
@@ -34,6 +34,52 @@ impl Server {
 				.await
 				.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
 			let p = connection.p();
+			let now = time::OffsetDateTime::now_utc().format(&Rfc3339).unwrap();
+			let timeout = self
+				.options
+				.advanced
+				.build_dequeue_timeout
+				.unwrap_or(Duration::from_secs(3600));
+			let time = (time::OffsetDateTime::now_utc() - timeout)
+				.format(&Rfc3339)
+				.unwrap();
+			let mut params = db::params![now, time];
+
+			// Only dequeue builds for the worker's hosts.
+			let host_condition =
+				if let Some(hosts) = arg.hosts.as_ref().filter(|hosts| !hosts.is_empty()) {
+					let placeholders = hosts
+						.iter()
+						.map(|host| {
+							params.extend(db::params![host]);
+							format!("{p}{}", params.len())
+						})
+						.collect::<Vec<_>>()
+						.join(", ");
+					format!("and host in ({placeholders})")
+				} else if arg.hosts.is_some() {
+					"and false".to_owned()
+				} else {
+					String::new()
+				};
+
+			// Only dequeue builds whose labels are all among the worker's labels.
+			let label_condition =
+				if arg.labels.is_empty() {
+					"build_labels.build = builds.id".to_owned()
+				} else {
+					let placeholders = arg
+						.labels
+						.iter()
+						.map(|label| {
+							params.extend(db::params![label]);
+							format!("{p}{}", params.len())
+						})
+						.collect::<Vec<_>>()
+						.join(", ");
+					format!("build_labels.build = builds.id and build_labels.label not in ({placeholders})")
+				};
+
 			let statement = formatdoc!(
 				"
 					update builds
@@ -42,24 +88,26 @@ impl Server {
 						select id
 						from builds
 						where
-							status = 'created' or
-							(status = 'dequeued' and dequeued_at <= {p}2)
-						order by created_at
+							(status = 'created' or (status = 'dequeued' and dequeued_at <= {p}2))
+							{host_condition}
+							and not exists (
+								select 1
+								from build_labels
+								where {label_condition}
+							)
+						order by
+							priority desc,
+							(
+								select count(*)
+								from builds running
+								where running.root = builds.root and running.status in ('dequeued', 'started')
+							),
+							created_at
 						limit 1
 					)
 					returning id;
 				"
 			);
-			let now = time::OffsetDateTime::now_utc().format(&Rfc3339).unwrap();
-			let timeout = self
-				.options
-				.advanced
-				.build_dequeue_timeout
-				.unwrap_or(Duration::from_secs(3600));
-			let time = (time::OffsetDateTime::now_utc() - timeout)
-				.format(&Rfc3339)
-				.unwrap();
-			let params = db::params![now, time];
 			let Some(id) = connection
 				.query_optional_value_into(statement, params)
 				.await
@@ -1,8 +1,9 @@
 use super::log;
 use crate::Server;
 use bytes::Bytes;
-use futures::{stream::FuturesUnordered, TryStreamExt as _};
+use futures::{future, stream::FuturesUnordered, StreamExt as _, TryStreamExt as _};
 use indoc::formatdoc;
+use std::collections::HashSet;
 use tangram_client as tg;
 use tangram_database::{self as db, prelude::*};
 use tangram_http::{incoming::request::Ext as _, outgoing::response::Ext as _, Incoming, Outgoing};
@@ -31,6 +32,26 @@ impl Server {
 			return Ok(output);
 		}
 
+		let timed_out = false;
+		self.finish_build_local(id, arg.outcome, timed_out).await
+	}
+
+	/// Finish a local build that exceeded its timeout. Its unfinished children are canceled as with `finish_build`, but the children canceled here do not change its outcome to canceled, so that it fails with the timeout error.
+	pub(crate) async fn finish_timed_out_build(
+		&self,
+		id: &tg::build::Id,
+		outcome: tg::build::outcome::Data,
+	) -> tg::Result<bool> {
+		let timed_out = true;
+		self.finish_build_local(id, outcome, timed_out).await
+	}
+
+	async fn finish_build_local(
+		&self,
+		id: &tg::build::Id,
+		outcome: tg::build::outcome::Data,
+		timed_out: bool,
+	) -> tg::Result<bool> {
 		// Get the build.
 		let Some(output) = self.try_get_build_local(id).await? else {
 			return Err(tg::error!("failed to find the build"));
@@ -73,27 +94,28 @@ impl Server {
 		drop(connection);
 
 		// Cancel unfinished children.
-		children
+		let canceled = children
 			.iter()
 			.map(|child| async move {
 				let arg = tg::build::finish::Arg {
 					outcome: tg::build::outcome::Data::Canceled,
 					remote: None,
 				};
-				self.finish_build(child, arg).await?;
-				Ok::<_, tg::Error>(())
+				let finished = self.finish_build(child, arg).await.ok()?;
+				finished.then(|| child.clone())
 			})
 			.collect::<FuturesUnordered<_>>()
-			.try_collect::<Vec<_>>()
-			.await
-			.ok();
+			.filter_map(future::ready)
+			.collect::<HashSet<_>>()
+			.await;
 
 		// Get the outcome.
-		let mut outcome = arg.outcome;
+		let mut outcome = outcome;
 
-		// If any of the children were canceled, then this build should be canceled.
+		// If any of the children were canceled, then this build should be canceled. If the build timed out, then the children that were canceled above are skipped, because they were canceled as a result of the timeout.
 		let outcomes = children
 			.iter()
+			.filter(|child_id| !(timed_out && canceled.contains(*child_id)))
 			.map(|child_id| async move {
 				// Check if the child is finished before awaiting its outcome.
 				let Some(tg::build::Status::Finished) =
@@ -127,14 +149,10 @@ impl Server {
 		if let Some(expected) = target.checksum(self).await?.clone() {
 			if let Ok(tg::value::Data::Object(object)) = outcome.try_unwrap_succeeded_ref().cloned()
 			{
-				if tg::artifact::Id::try_from(object.clone()).is_ok() {
+				if let Ok(artifact) = tg::artifact::Id::try_from(object.clone()) {
+					let artifact = tg::Artifact::with_id(artifact);
 					let algorithm = expected.algorithm();
-					let actual = match algorithm {
-						tg::checksum::Algorithm::Unsafe => tg::Checksum::Unsafe,
-						_ => {
-							return Err(tg::error!("unimplemented"));
-						},
-					};
+					let actual = self.checksum_artifact(&artifact, algorithm).await?;
 					if expected != tg::Checksum::Unsafe && expected != actual {
 						outcome = tg::build::outcome::Data::Failed(tg::error!(
 							%expected,
@@ -242,14 +260,16 @@ impl Server {
 					heartbeat_at = null,
 					log = {p}1,
 					outcome = {p}2,
-					status = {p}3,
-					finished_at = {p}4
-				where id = {p}5;
+					outcome_kind = {p}3,
+					status = {p}4,
+					finished_at = {p}5
+				where id = {p}6;
 			"
 		);
+		let outcome_kind = outcome.kind();
 		let status = tg::build::Status::Finished;
 		let finished_at = time::OffsetDateTime::now_utc().format(&Rfc3339).unwrap();
-		let params = db::params![log, outcome, status, finished_at, id];
+		let params = db::params![log, outcome, outcome_kind, status, finished_at, id];
 		connection
 			.execute(statement, params)
 			.await
@@ -305,3 +325,78 @@ impl Server {
 		Ok(response)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::TestServer;
+	use std::collections::BTreeSet;
+
+	/// Create a parent build with a child that never finishes.
+	async fn put_builds(server: &Server) -> (tg::build::Id, tg::build::Id) {
+		let target = tg::Target::builder("builtin").build();
+		let target = target.id(server).await.unwrap();
+		let child = tg::build::Id::new();
+		let parent = tg::build::Id::new();
+		for (id, children) in [(&child, vec![]), (&parent, vec![child.clone()])] {
+			let arg = tg::build::put::Arg {
+				id: id.clone(),
+				children,
+				host: "builtin".to_owned(),
+				labels: BTreeSet::new(),
+				log: None,
+				outcome: None,
+				priority: 0,
+				retry: tg::build::Retry::Failed,
+				root: None,
+				status: tg::build::Status::Started,
+				target: target.clone(),
+				timeout: None,
+				created_at: time::OffsetDateTime::now_utc(),
+				dequeued_at: None,
+				started_at: None,
+				finished_at: None,
+			};
+			server.put_build(id, arg).await.unwrap();
+		}
+		(parent, child)
+	}
+
+	async fn outcome(server: &Server, id: &tg::build::Id) -> tg::build::outcome::Data {
+		server
+			.try_get_build_local(id)
+			.await
+			.unwrap()
+			.unwrap()
+			.outcome
+			.unwrap()
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn finish_with_hanging_child() {
+		let server = TestServer::new().await;
+
+		// If a build that timed out is finished, then its child is canceled and it keeps its outcome.
+		let (parent, child) = put_builds(&server).await;
+		let outcome_ = tg::build::outcome::Data::Failed(tg::error!("the build timed out"));
+		assert!(server
+			.finish_timed_out_build(&parent, outcome_)
+			.await
+			.unwrap());
+		assert!(outcome(&server, &child).await.try_unwrap_canceled().is_ok());
+		assert!(outcome(&server, &parent).await.try_unwrap_failed().is_ok());
+
+		// If any other build is finished, then its child is canceled and so is it.
+		let (parent, child) = put_builds(&server).await;
+		let arg = tg::build::finish::Arg {
+			outcome: tg::build::outcome::Data::Failed(tg::error!("the build failed")),
+			remote: None,
+		};
+		assert!(server.finish_build(&parent, arg).await.unwrap());
+		assert!(outcome(&server, &child).await.try_unwrap_canceled().is_ok());
+		assert!(outcome(&server, &parent)
+			.await
+			.try_unwrap_canceled()
+			.is_ok());
+	}
+}
@@ -47,12 +47,15 @@ impl Server {
 					outcomes_complete,
 					outcomes_count,
 					outcomes_weight,
+					priority,
 					retry,
+					root,
 					status,
 					target,
 					targets_complete,
 					targets_count,
 					targets_weight,
+					timeout,
 					created_at,
 					dequeued_at,
 					started_at,
@@ -62,15 +65,35 @@ impl Server {
 			"
 		);
 		let params = db::params![id];
-		let output = connection
-			.query_optional_into(statement, params)
+		let Some(mut output) = connection
+			.query_optional_into::<tg::build::get::Output>(statement, params)
 			.await
-			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?
+		else {
+			return Ok(None);
+		};
+
+		// Get the build's labels.
+		let p = connection.p();
+		let statement = formatdoc!(
+			"
+				select label
+				from build_labels
+				where build = {p}1;
+			"
+		);
+		let params = db::params![id];
+		output.labels = connection
+			.query_all_value_into(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?
+			.into_iter()
+			.collect();
 
 		// Drop the database connection.
 		drop(connection);
 
-		Ok(output)
+		Ok(Some(output))
 	}
 
 	async fn try_get_build_remote(
@@ -110,11 +133,15 @@ impl Server {
 						id: output.id.clone(),
 						children,
 						host: output.host.clone(),
+						labels: output.labels.clone(),
 						log: output.log.clone(),
 						outcome: output.outcome.clone(),
+						priority: output.priority,
 						retry: output.retry,
+						root: output.root.clone(),
 						status: output.status,
 						target: output.target.clone(),
+						timeout: output.timeout,
 						created_at: output.created_at,
 						dequeued_at: output.dequeued_at,
 						started_at: output.started_at,
@@ -0,0 +1,202 @@
+use crate::Server;
+use indoc::formatdoc;
+use tangram_client as tg;
+use tangram_database::{self as db, prelude::*};
+use tangram_http::{incoming::request::Ext as _, outgoing::response::Ext as _, Incoming, Outgoing};
+use time::format_description::well_known::Rfc3339;
+
+impl Server {
+	pub async fn list_builds(
+		&self,
+		arg: tg::build::list::Arg,
+	) -> tg::Result<tg::build::list::Output> {
+		// If the remote arg is set, then forward the request.
+		if let Some(remote) = arg.remote.as_ref() {
+			let remote = self
+				.remotes
+				.get(remote)
+				.ok_or_else(|| tg::error!("the remote does not exist"))?
+				.clone();
+			let arg = tg::build::list::Arg {
+				remote: None,
+				..arg
+			};
+			let output = remote.list_builds(arg).await?;
+			return Ok(output);
+		}
+
+		// Get a database connection.
+		let connection = self
+			.database
+			.connection(db::Priority::Low)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
+
+		// Create the conditions.
+		let p = connection.p();
+		let mut conditions = Vec::new();
+		let mut params = Vec::new();
+		if let Some(status) = arg.status {
+			params.extend(db::params![status]);
+			conditions.push(format!("status = {p}{}", params.len()));
+		}
+		if let Some(host) = arg.host.as_ref() {
+			params.extend(db::params![host]);
+			conditions.push(format!("host = {p}{}", params.len()));
+		}
+		if let Some(target) = arg.target.as_ref() {
+			params.extend(db::params![target]);
+			conditions.push(format!("target = {p}{}", params.len()));
+		}
+		if let Some(created_after) = arg.created_after {
+			params.extend(db::params![created_after.format(&Rfc3339).unwrap()]);
+			conditions.push(format!("created_at >= {p}{}", params.len()));
+		}
+		if let Some(created_before) = arg.created_before {
+			params.extend(db::params![created_before.format(&Rfc3339).unwrap()]);
+			conditions.push(format!("created_at < {p}{}", params.len()));
+		}
+		if let Some(outcome) = arg.outcome {
+			params.extend(db::params![outcome]);
+			conditions.push(format!("outcome_kind = {p}{}", params.len()));
+		}
+		let conditions = if conditions.is_empty() {
+			String::new()
+		} else {
+			format!("where {}", conditions.join(" and "))
+		};
+
+		// Get the builds, most recently created first.
+		let length = arg
+			.length
+			.map_or(i64::MAX, |length| length.try_into().unwrap_or(i64::MAX));
+		let offset = arg
+			.offset
+			.map_or(0, |offset| offset.try_into().unwrap_or(i64::MAX));
+		params.extend(db::params![length, offset]);
+		let length = params.len() - 1;
+		let offset = params.len();
+		let statement = formatdoc!(
+			"
+				select
+					id,
+					count,
+					host,
+					log,
+					logs_complete,
+					logs_count,
+					logs_weight,
+					outcome,
+					outcomes_complete,
+					outcomes_count,
+					outcomes_weight,
+					priority,
+					retry,
+					status,
+					target,
+					targets_complete,
+					targets_count,
+					targets_weight,
+					timeout,
+					created_at,
+					dequeued_at,
+					started_at,
+					finished_at
+				from builds
+				{conditions}
+				order by created_at desc
+				limit {p}{length}
+				offset {p}{offset};
+			"
+		);
+		let data = connection
+			.query_all_into(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+
+		// Drop the database connection.
+		drop(connection);
+
+		Ok(tg::build::list::Output { data })
+	}
+}
+
+impl Server {
+	pub(crate) async fn handle_list_builds_request<H>(
+		handle: &H,
+		request: http::Request<Incoming>,
+	) -> tg::Result<http::Response<Outgoing>>
+	where
+		H: tg::Handle,
+	{
+		let arg = request.query_params().transpose()?.unwrap_or_default();
+		let output = handle.list_builds(arg).await?;
+		let response = http::Response::builder().json(output).unwrap();
+		Ok(response)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::TestServer;
+	use std::collections::BTreeSet;
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn outcome_filter() {
+		let server = TestServer::new().await;
+
+		// Create a build for each outcome.
+		let target = tg::Target::builder("builtin").build();
+		let target = target.id(&*server).await.unwrap();
+		let outcomes = [
+			tg::build::outcome::Data::Canceled,
+			tg::build::outcome::Data::Failed(tg::error!("the build failed")),
+			tg::build::outcome::Data::Succeeded(tg::value::Data::Null),
+		];
+		let mut ids = Vec::new();
+		for outcome in outcomes {
+			let id = tg::build::Id::new();
+			let arg = tg::build::put::Arg {
+				id: id.clone(),
+				children: vec![],
+				host: "builtin".to_owned(),
+				labels: BTreeSet::new(),
+				log: None,
+				outcome: Some(outcome),
+				priority: 0,
+				retry: tg::build::Retry::Failed,
+				root: None,
+				status: tg::build::Status::Finished,
+				target: target.clone(),
+				timeout: None,
+				created_at: time::OffsetDateTime::now_utc(),
+				dequeued_at: None,
+				started_at: None,
+				finished_at: Some(time::OffsetDateTime::now_utc()),
+			};
+			server.put_build(&id, arg).await.unwrap();
+			ids.push(id);
+		}
+
+		// Each outcome kind must match only its build.
+		let kinds = [
+			tg::build::outcome::Kind::Canceled,
+			tg::build::outcome::Kind::Failed,
+			tg::build::outcome::Kind::Succeeded,
+		];
+		for (kind, id) in kinds.into_iter().zip(&ids) {
+			let arg = tg::build::list::Arg {
+				outcome: Some(kind),
+				..Default::default()
+			};
+			let output = server.list_builds(arg).await.unwrap();
+			let listed = output
+				.data
+				.into_iter()
+				.map(|output| output.id)
+				.collect::<Vec<_>>();
+			assert_eq!(listed, std::slice::from_ref(id));
+		}
+	}
+}
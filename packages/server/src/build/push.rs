@@ -111,11 +111,15 @@ impl Server {
 			id: build.clone(),
 			children,
 			host: output.host,
+			labels: output.labels,
 			log: output.log.clone(),
 			outcome: output.outcome,
+			priority: output.priority,
 			retry: output.retry,
+			root: output.root,
 			status: output.status,
 			target: output.target.clone(),
+			timeout: output.timeout,
 			created_at: output.created_at,
 			dequeued_at: output.dequeued_at,
 			started_at: output.started_at,
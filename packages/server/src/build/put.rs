@@ -14,7 +14,7 @@ impl Server {
 		id: &tg::build::Id,
 		arg: tg::build::put::Arg,
 	) -> tg::Result<tg::build::put::Output> {
-		// Insert the build, build children, and build objects.
+		// Insert the build, build children, build labels, and build objects.
 		{
 			// Get a database connection.
 			let mut connection = self
@@ -39,9 +39,13 @@ impl Server {
 						host,
 						log,
 						outcome,
+						outcome_kind,
+						priority,
 						retry,
+						root,
 						status,
 						target,
+						timeout,
 						touched_at,
 						created_at,
 						dequeued_at,
@@ -60,20 +64,28 @@ impl Server {
 						{p}9,
 						{p}10,
 						{p}11,
-						{p}12
+						{p}12,
+						{p}13,
+						{p}14,
+						{p}15,
+						{p}16
 					)
 					on conflict (id) do update set
 						host = {p}2,
 						log = {p}3,
 						outcome = {p}4,
-						retry = {p}5,
-						status = {p}6,
-						target = {p}7,
-						touched_at = {p}8,
-						created_at = {p}9,
-						dequeued_at = {p}10,
-						started_at = {p}11,
-						finished_at = {p}12;
+						outcome_kind = {p}5,
+						priority = {p}6,
+						retry = {p}7,
+						root = {p}8,
+						status = {p}9,
+						target = {p}10,
+						timeout = {p}11,
+						touched_at = {p}12,
+						created_at = {p}13,
+						dequeued_at = {p}14,
+						started_at = {p}15,
+						finished_at = {p}16;
 				"
 			);
 			let params = db::params![
@@ -81,9 +93,13 @@ impl Server {
 				arg.host,
 				arg.log,
 				arg.outcome,
+				arg.outcome.as_ref().map(tg::build::outcome::Data::kind),
+				arg.priority,
 				arg.retry,
+				arg.root,
 				arg.status,
 				arg.target,
+				arg.timeout.map(|timeout| timeout.as_secs_f64()),
 				time::OffsetDateTime::now_utc().format(&Rfc3339).unwrap(),
 				arg.created_at.format(&Rfc3339).unwrap(),
 				arg.dequeued_at.map(|t| t.format(&Rfc3339).unwrap()),
@@ -138,6 +154,49 @@ impl Server {
 				.try_collect::<()>()
 				.await?;
 
+			// Delete any existing labels.
+			let p = transaction.p();
+			let statement = formatdoc!(
+				"
+					delete from build_labels
+					where build = {p}1;
+				"
+			);
+			let params = db::params![id];
+			transaction
+				.execute(statement, params)
+				.await
+				.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+
+			// Insert the labels.
+			let p = transaction.p();
+			let statement = formatdoc!(
+				"
+					insert into build_labels (build, label)
+					values ({p}1, {p}2)
+					on conflict (build, label) do nothing;
+				"
+			);
+			arg.labels
+				.iter()
+				.map(|label| {
+					let transaction = transaction.clone();
+					let statement = statement.clone();
+					async move {
+						let params = db::params![id, label];
+						transaction
+							.execute(statement, params)
+							.await
+							.map_err(|source| {
+								tg::error!(!source, "failed to execute the statement")
+							})?;
+						Ok::<_, tg::Error>(())
+					}
+				})
+				.collect::<FuturesUnordered<_>>()
+				.try_collect::<()>()
+				.await?;
+
 			// Delete any existing objects.
 			let p = transaction.p();
 			let statement = formatdoc!(
@@ -14,10 +14,19 @@ impl Server {
 			let permit = self.build_semaphore.clone().acquire_owned().await.unwrap();
 			let permit = BuildPermit(Either::Left(permit));
 
-			// Try to dequeue a build locally or from one of the remotes.
-			let arg = tg::build::dequeue::Arg::default();
+			// Try to dequeue a build locally or from one of the remotes. Only dequeue builds for hosts that this server has a runtime for and whose labels this server has.
+			let hosts = self.runtimes.read().unwrap().keys().cloned().collect();
+			let arg = tg::build::dequeue::Arg {
+				hosts: Some(hosts),
+				labels: self
+					.options
+					.build
+					.as_ref()
+					.map(|build| build.labels.clone())
+					.unwrap_or_default(),
+			};
 			let futures = std::iter::once(
-				self.dequeue_build(arg)
+				self.dequeue_build(arg.clone())
 					.map_ok(|output| (output, None))
 					.boxed(),
 			)
@@ -27,7 +36,7 @@ impl Server {
 					.iter()
 					.filter(|(_, remote)| remote.build)
 					.map(|(name, remote)| {
-						let arg = tg::build::dequeue::Arg::default();
+						let arg = arg.clone();
 						remote
 							.client
 							.dequeue_build(arg)
@@ -108,8 +117,32 @@ impl Server {
 			self.build_permits.remove(build.id());
 		}
 
-		// Build.
-		let result = self.build_task_inner(build.clone(), remote.clone()).await;
+		// Get the timeout. If the build does not have one, then use the server's default.
+		let timeout = self
+			.try_get_build(build.id())
+			.await?
+			.and_then(|output| output.timeout)
+			.or_else(|| {
+				self.options
+					.build
+					.as_ref()
+					.and_then(|options| options.timeout)
+			});
+
+		// Build. If the build exceeds its timeout, then it fails, and finishing it cancels its unfinished children.
+		let future = self.build_task_inner(build.clone(), remote.clone());
+		let mut timed_out = false;
+		let result = if let Some(timeout) = timeout {
+			tokio::time::timeout(timeout, future)
+				.await
+				.unwrap_or_else(|_| {
+					timed_out = true;
+					let timeout = timeout.as_secs_f64();
+					Err(tg::error!(%timeout, "the build timed out"))
+				})
+		} else {
+			future.await
+		};
 		let outcome = match result {
 			Ok(outcome) => outcome,
 			Err(error) => tg::build::Outcome::Failed(error),
@@ -130,15 +163,21 @@ impl Server {
 			}
 		}
 
-		// Finish the build.
-		let arg = tg::build::finish::Arg {
-			outcome,
-			remote: remote.clone(),
-		};
-		build
-			.finish(self, arg)
-			.await
-			.map_err(|source| tg::error!(!source, "failed to finish the build"))?;
+		// Finish the build. If a local build timed out, then finish it so that the children it cancels do not change its outcome.
+		if timed_out && remote.is_none() {
+			self.finish_timed_out_build(build.id(), outcome)
+				.await
+				.map_err(|source| tg::error!(!source, "failed to finish the build"))?;
+		} else {
+			let arg = tg::build::finish::Arg {
+				outcome,
+				remote: remote.clone(),
+			};
+			build
+				.finish(self, arg)
+				.await
+				.map_err(|source| tg::error!(!source, "failed to finish the build"))?;
+		}
 
 		Ok::<_, tg::Error>(())
 	}
@@ -1,177 +1,231 @@
 use super::Server;
 use indoc::formatdoc;
+use std::path::Path;
 use tangram_client as tg;
 use tangram_database::{self as db, prelude::*};
+use time::format_description::well_known::Rfc3339;
 
 impl Server {
-	pub async fn clean(&self) -> tg::Result<()> {
-		// Clean the checkouts directory.
-		tokio::fs::remove_dir_all(self.checkouts_path())
-			.await
-			.map_err(|source| tg::error!(!source, "failed to remove the checkouts directory"))?;
-		tokio::fs::create_dir_all(self.checkouts_path())
-			.await
-			.map_err(|error| {
-				tg::error!(source = error, "failed to recreate the checkouts directory")
-			})?;
-
-		// Clean the temporary directory.
-		tokio::fs::remove_dir_all(self.tmp_path())
-			.await
-			.map_err(|source| tg::error!(!source, "failed to remove the temporary directory"))?;
-		tokio::fs::create_dir_all(self.tmp_path())
-			.await
-			.map_err(|error| {
-				tg::error!(source = error, "failed to recreate the temporary directory")
-			})?;
+	pub async fn clean(
+		&self,
+		arg: tg::server::clean::Arg,
+	) -> tg::Result<tg::server::clean::Output> {
+		// Get the time before which unreachable builds and objects may be removed.
+		let max_touched_at = arg
+			.max_age
+			.map(|max_age| {
+				let max_age = time::Duration::try_from(max_age)
+					.map_err(|source| tg::error!(!source, "invalid max age"))?;
+				let max_touched_at = (time::OffsetDateTime::now_utc() - max_age)
+					.format(&Rfc3339)
+					.unwrap();
+				Ok::<_, tg::Error>(max_touched_at)
+			})
+			.transpose()?;
 
 		// Get a database connection.
-		let connection = self
+		let mut connection = self
 			.database
 			.connection(db::Priority::Low)
 			.await
 			.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
 
-		// Remove builds.
-		loop {
-			// Get a build to remove.
-			let statement = formatdoc!(
-				"
+		// Begin a transaction, so that the builds and objects that are found to be unreachable are removed atomically.
+		let transaction = connection
+			.transaction()
+			.await
+			.map_err(|source| tg::error!(!source, "failed to begin a transaction"))?;
+
+		// Mark the builds and objects that are reachable from the roots. The roots are tagged items, pinned items, builds that are not finished, and builds and objects that were touched more recently than the max age.
+		let p = transaction.p();
+		let reachable = formatdoc!(
+			"
+				with recursive reachable_builds (id) as (
+					select item
+					from tags
+					where item is not null
+					union
+					select item
+					from roots
+					union
 					select id
 					from builds
-					where (
-						select count(*) = 0
-						from build_children
-						where child = builds.id
-					)
-					limit 100;
-				"
-			);
-			let params = db::params![];
-			let builds = connection
-				.query_all_value_into::<tg::build::Id>(statement, params)
-				.await
-				.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
-
-			// If there are no builds, then break.
-			if builds.is_empty() {
-				break;
-			}
+					where status != 'finished' or touched_at >= {p}1
+					union
+					select build_children.child
+					from build_children
+					join reachable_builds on build_children.build = reachable_builds.id
+				),
+				reachable_objects (id) as (
+					select item
+					from tags
+					where item is not null
+					union
+					select item
+					from roots
+					union
+					select id
+					from objects
+					where touched_at >= {p}1
+					union
+					select build_objects.object
+					from build_objects
+					join reachable_builds on build_objects.build = reachable_builds.id
+					union
+					select object_children.child
+					from object_children
+					join reachable_objects on object_children.object = reachable_objects.id
+				)
+			"
+		);
 
-			for id in builds {
-				// Remove the build.
-				let p = connection.p();
-				let statement = formatdoc!(
-					"
-						delete from builds
-						where id = {p}1;
-					"
-				);
-				let params = db::params![id];
-				connection
-					.execute(statement, params)
-					.await
-					.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+		// Get the builds that are not reachable.
+		let statement = formatdoc!(
+			"
+				{reachable}
+				select id
+				from builds
+				where id not in (select id from reachable_builds);
+			"
+		);
+		let params = db::params![max_touched_at];
+		let builds = transaction
+			.query_all_value_into::<tg::build::Id>(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+
+		// Get the objects that are not reachable. The weight of each object includes the weights of its children, so subtract them to get the number of bytes that removing the object reclaims.
+		#[derive(serde::Deserialize)]
+		struct Row {
+			id: tg::object::Id,
+			weight: Option<u64>,
+		}
+		let statement = formatdoc!(
+			"
+				{reachable}
+				select id, weight - coalesce((
+					select sum(children.weight)
+					from object_children
+					join objects children on children.id = object_children.child
+					where object_children.object = objects.id
+				), 0) as weight
+				from objects
+				where id not in (select id from reachable_objects);
+			"
+		);
+		let params = db::params![max_touched_at];
+		let objects = transaction
+			.query_all_into::<Row>(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+
+		// Create the output.
+		let output = tg::server::clean::Output {
+			builds: builds.len().try_into().unwrap(),
+			objects: objects.len().try_into().unwrap(),
+			bytes: objects.iter().filter_map(|row| row.weight).sum(),
+		};
 
-				// Remove the build children.
-				let p = connection.p();
+		// If this is a dry run, then return the output without removing anything.
+		if arg.dry_run {
+			return Ok(output);
+		}
+
+		// Remove the builds.
+		for id in &builds {
+			for table in [
+				"builds",
+				"build_children",
+				"build_labels",
+				"build_logs",
+				"build_objects",
+			] {
+				let column = if table == "builds" { "id" } else { "build" };
 				let statement = formatdoc!(
 					"
-						delete from build_children
-						where object = {p}1;
+						delete from {table}
+						where {column} = {p}1;
 					"
 				);
 				let params = db::params![id];
-				connection
+				transaction
 					.execute(statement, params)
 					.await
 					.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+			}
+		}
 
-				// Remove the build objects.
-				let p = connection.p();
+		// Remove the objects.
+		for Row { id, .. } in &objects {
+			for table in ["objects", "object_children"] {
+				let column = if table == "objects" { "id" } else { "object" };
 				let statement = formatdoc!(
 					"
-						delete from build_objects
-						where object = {p}1;
+						delete from {table}
+						where {column} = {p}1;
 					"
 				);
 				let params = db::params![id];
-				connection
+				transaction
 					.execute(statement, params)
 					.await
 					.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
 			}
 		}
 
-		// Remove objects.
-		loop {
-			// Get an object to remove.
-			let statement = formatdoc!(
-				"
-					select id
-					from objects
-					where (
-						select count(*) = 0
-						from object_children
-						where child = objects.id
-					) and (
-						select count(*) = 0
-						from build_objects
-						where object = objects.id
-					) and (
-						select count(*) = 0
-						from package_versions
-						where artifact = objects.id
-					) and (
-						select count(*) = 0
-						from roots
-						where id = objects.id
-					)
-					limit 100;
-				"
-			);
-			let params = db::params![];
-			let objects = connection
-				.query_all_value_into::<tg::object::Id>(statement, params)
-				.await
-				.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
-
-			// If there are no objects, then break.
-			if objects.is_empty() {
-				break;
-			}
+		// Commit the transaction.
+		transaction
+			.commit()
+			.await
+			.map_err(|source| tg::error!(!source, "failed to commit the transaction"))?;
 
-			for id in objects {
-				// Remove the object.
-				let p = connection.p();
-				let statement = formatdoc!(
-					"
-						delete from objects
-						where id = {p}1;
-					"
-				);
-				let params = db::params![id];
-				connection
-					.execute(statement, params)
-					.await
-					.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+		// Drop the connection.
+		drop(connection);
 
-				// Remove the object children.
-				let p = connection.p();
-				let statement = formatdoc!(
-					"
-						delete from object_children
-						where object = {p}1;
-					"
-				);
-				let params = db::params![id];
-				connection
-					.execute(statement, params)
-					.await
-					.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
-			}
+		// Remove the builds' log files.
+		for id in &builds {
+			let path = self.logs_path().join(id.to_string());
+			remove_path(&path).await?;
+		}
+
+		// Remove the objects' blob files and checkouts.
+		for Row { id, .. } in &objects {
+			let path = self.blobs_path().join(id.to_string());
+			remove_path(&path).await?;
+			let path = self.checkouts_path().join(id.to_string());
+			remove_path(&path).await?;
 		}
 
-		Ok(())
+		// Clean the temporary directory.
+		tokio::fs::remove_dir_all(self.tmp_path())
+			.await
+			.map_err(|source| tg::error!(!source, "failed to remove the temporary directory"))?;
+		tokio::fs::create_dir_all(self.tmp_path())
+			.await
+			.map_err(|error| {
+				tg::error!(source = error, "failed to recreate the temporary directory")
+			})?;
+
+		Ok(output)
 	}
 }
+
+/// Remove a file, directory, or symlink if it exists.
+async fn remove_path(path: &Path) -> tg::Result<()> {
+	let metadata = match tokio::fs::symlink_metadata(path).await {
+		Ok(metadata) => metadata,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+		Err(source) => {
+			return Err(tg::error!(!source, %path = path.display(), "failed to get the metadata"))
+		},
+	};
+	let result = if metadata.is_dir() {
+		tokio::fs::remove_dir_all(path).await
+	} else {
+		tokio::fs::remove_file(path).await
+	};
+	result.map_err(
+		|source| tg::error!(!source, %path = path.display(), "failed to remove the path"),
+	)?;
+	Ok(())
+}
@@ -12,6 +12,7 @@ use std::{
 	path::{Path, PathBuf},
 	pin::pin,
 	sync::{Arc, Mutex, RwLock},
+	time::Duration,
 };
 use tangram_client as tg;
 use tangram_database as db;
@@ -25,12 +26,14 @@ use tokio::{
 use url::Url;
 
 mod artifact;
+mod authorization;
 mod blob;
 mod build;
 mod clean;
 mod compiler;
 mod database;
 mod lockfile;
+mod login;
 mod messenger;
 mod migrations;
 mod object;
@@ -38,10 +41,13 @@ mod package;
 mod progress;
 mod reference;
 mod remote;
+mod root;
 mod runtime;
 mod server;
 mod tag;
 mod target;
+#[cfg(test)]
+mod test;
 mod tmp;
 mod user;
 mod util;
@@ -51,6 +57,8 @@ pub use self::options::Options;
 
 pub mod options;
 
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// A server.
 #[derive(Clone)]
 pub struct Server(Arc<Inner>);
@@ -261,6 +269,9 @@ impl Server {
 			vfs,
 		}));
 
+		// Add the remotes from the database.
+		server.load_remotes().await?;
+
 		// Start the task.
 		let task = Task::spawn(|stop| {
 			let server = server.clone();
@@ -332,6 +343,12 @@ impl Server {
 			let runtime = self::runtime::Runtime::Js(runtime);
 			self.runtimes.write().unwrap().insert(triple, runtime);
 		}
+		{
+			let triple = "wasm32-wasi".to_owned();
+			let runtime = self::runtime::wasm::Runtime::new(self)?;
+			let runtime = self::runtime::Runtime::Wasm(runtime);
+			self.runtimes.write().unwrap().insert(triple, runtime);
+		}
 		#[cfg(all(target_arch = "aarch64", target_os = "macos"))]
 		{
 			let triple = "aarch64-darwin".to_owned();
@@ -360,6 +377,14 @@ impl Server {
 			let runtime = self::runtime::Runtime::Linux(runtime);
 			self.runtimes.write().unwrap().insert(triple, runtime);
 		}
+		for (triple, options) in &self.options.runtimes {
+			let runtime = self::runtime::external::Runtime::new(self, options.clone());
+			let runtime = self::runtime::Runtime::External(runtime);
+			self.runtimes
+				.write()
+				.unwrap()
+				.insert(triple.clone(), runtime);
+		}
 
 		// Start the build heartbeat monitor task.
 		let build_heartbeat_monitor_task =
@@ -405,7 +430,14 @@ impl Server {
 		};
 
 		// Serve.
-		Self::serve(self.clone(), self.options.url.clone(), stop.clone()).await?;
+		Self::serve(
+			self.clone(),
+			self.options.url.clone(),
+			self.options.authorization.clone(),
+			self.options.tls.clone(),
+			stop.clone(),
+		)
+		.await?;
 
 		// Abort the build spawn task.
 		if let Some(task) = build_spawn_task {
@@ -490,7 +522,13 @@ impl Server {
 }
 
 impl Server {
-	async fn serve<H>(handle: H, url: Url, stop: Stop) -> tg::Result<()>
+	async fn serve<H>(
+		handle: H,
+		url: Url,
+		authorization: Option<self::options::Authorization>,
+		tls: Option<self::options::Tls>,
+		stop: Stop,
+	) -> tg::Result<()>
 	where
 		H: tg::Handle,
 	{
@@ -508,7 +546,7 @@ impl Server {
 					.map_err(|source| tg::error!(!source, "failed to bind"))?;
 				tokio_util::either::Either::Left(listener)
 			},
-			"http" => {
+			"http" | "https" => {
 				let host = url.host().ok_or_else(|| tg::error!("invalid url"))?;
 				let port = url
 					.port_or_known_default()
@@ -523,6 +561,15 @@ impl Server {
 			},
 		};
 
+		// Create the TLS acceptor.
+		let acceptor = if url.scheme() == "https" {
+			let tls =
+				tls.ok_or_else(|| tg::error!("TLS must be configured to serve on an https url"))?;
+			Some(Self::create_tls_acceptor(&tls)?)
+		} else {
+			None
+		};
+
 		tracing::trace!("serving on {url}");
 
 		loop {
@@ -550,7 +597,7 @@ impl Server {
 						)
 					},
 				};
-				Ok::<_, tg::Error>(TokioIo::new(stream))
+				Ok::<_, tg::Error>(stream)
 			};
 			let stream = match future::select(pin!(accept), pin!(stop.stopped())).await {
 				future::Either::Left((result, _)) => result?,
@@ -562,13 +609,16 @@ impl Server {
 			// Create the service.
 			let service = hyper::service::service_fn({
 				let handle = handle.clone();
+				let authorization = authorization.clone();
 				let stop = stop.clone();
 				move |mut request| {
 					let handle = handle.clone();
+					let authorization = authorization.clone();
 					let stop = stop.clone();
 					async move {
 						request.extensions_mut().insert(stop);
-						let response = Self::handle_request(&handle, request).await;
+						let response =
+							Self::handle_request(&handle, request, authorization.as_ref()).await;
 						Ok::<_, Infallible>(response)
 					}
 				}
@@ -576,8 +626,29 @@ impl Server {
 
 			// Spawn a task to serve the connection.
 			task_tracker.spawn({
+				let acceptor = acceptor.clone();
 				let stop = stop.clone();
 				async move {
+					// Perform the TLS handshake if necessary.
+					let stream = match (stream, acceptor) {
+						(tokio_util::either::Either::Right(stream), Some(acceptor)) => {
+							let accept = acceptor.accept(stream);
+							match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, accept).await {
+								Ok(Ok(stream)) => tokio_util::either::Either::Right(stream),
+								Ok(Err(error)) => {
+									tracing::trace!(?error, "failed to perform the TLS handshake");
+									return;
+								},
+								Err(_) => {
+									tracing::trace!("the TLS handshake timed out");
+									return;
+								},
+							}
+						},
+						(stream, _) => tokio_util::either::Either::Left(stream),
+					};
+					let stream = TokioIo::new(stream);
+
 					let builder =
 						hyper_util::server::conn::auto::Builder::new(TokioExecutor::new());
 					let connection = builder.serve_connection_with_upgrades(stream, service);
@@ -601,9 +672,49 @@ impl Server {
 		Ok(())
 	}
 
+	fn create_tls_acceptor(tls: &self::options::Tls) -> tg::Result<tokio_rustls::TlsAcceptor> {
+		// Read the certificate chain and the key.
+		let certificates = tg::tls::read_certificates(&tls.certificate)?;
+		let key = tg::tls::read_key(&tls.key)?;
+
+		// Create the client certificate verifier. If no certificate authorities are configured, then client certificates are not requested.
+		let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+		let builder = rustls::ServerConfig::builder_with_provider(provider.clone())
+			.with_safe_default_protocol_versions()
+			.map_err(|source| tg::error!(!source, "failed to create the TLS config"))?;
+		let builder = if let Some(path) = &tls.client_certificate_authorities {
+			let mut roots = rustls::RootCertStore::empty();
+			for certificate in tg::tls::read_certificates(path)? {
+				roots.add(certificate).map_err(|source| {
+					tg::error!(!source, "failed to add the client certificate authority")
+				})?;
+			}
+			let verifier = rustls::server::WebPkiClientVerifier::builder_with_provider(
+				Arc::new(roots),
+				provider,
+			)
+			.build()
+			.map_err(|source| {
+				tg::error!(!source, "failed to create the client certificate verifier")
+			})?;
+			builder.with_client_cert_verifier(verifier)
+		} else {
+			builder.with_no_client_auth()
+		};
+
+		// Create the config.
+		let mut config = builder
+			.with_single_cert(certificates, key)
+			.map_err(|source| tg::error!(!source, "invalid certificate or key"))?;
+		config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+		Ok(tokio_rustls::TlsAcceptor::from(Arc::new(config)))
+	}
+
 	async fn handle_request<H>(
 		handle: &H,
 		mut request: http::Request<Incoming>,
+		authorization: Option<&self::options::Authorization>,
 	) -> http::Response<Outgoing>
 	where
 		H: tg::Handle,
@@ -613,6 +724,19 @@ impl Server {
 
 		tracing::trace!(?id, method = ?request.method(), path = ?request.uri().path(), "received request");
 
+		// Authorize the request. If it is rejected, then return the rejection.
+		if let Some(authorization) = authorization {
+			if let Some(mut response) =
+				Self::authorize_request(handle, &request, authorization).await
+			{
+				let key = http::HeaderName::from_static("x-tangram-request-id");
+				let value = http::HeaderValue::from_str(&id.to_string()).unwrap();
+				response.headers_mut().insert(key, value);
+				tracing::trace!(?id, status = ?response.status(), "rejected request");
+				return response;
+			}
+		}
+
 		let method = request.method().clone();
 		let path = request.uri().path().to_owned();
 		let path_components = path.split('/').skip(1).collect_vec();
@@ -634,6 +758,9 @@ impl Server {
 			},
 
 			// Builds.
+			(http::Method::GET, ["builds"]) => {
+				Self::handle_list_builds_request(handle, request).boxed()
+			},
 			(http::Method::GET, ["builds", build]) => {
 				Self::handle_get_build_request(handle, request, build).boxed()
 			},
@@ -727,6 +854,20 @@ impl Server {
 				Self::handle_delete_remote_request(handle, request, name).boxed()
 			},
 
+			// Roots.
+			(http::Method::GET, ["roots"]) => {
+				Self::handle_list_roots_request(handle, request).boxed()
+			},
+			(http::Method::GET, ["roots", name]) => {
+				Self::handle_get_root_request(handle, request, name).boxed()
+			},
+			(http::Method::PUT, ["roots", name]) => {
+				Self::handle_put_root_request(handle, request, name).boxed()
+			},
+			(http::Method::DELETE, ["roots", name]) => {
+				Self::handle_delete_root_request(handle, request, name).boxed()
+			},
+
 			// Runtimes.
 			(http::Method::GET, ["runtimes", "js", "doc"]) => {
 				Self::handle_get_js_runtime_doc_request(handle, request).boxed()
@@ -758,9 +899,26 @@ impl Server {
 			(http::Method::POST, ["targets", target, "build"]) => {
 				Self::handle_build_target_request(handle, request, target).boxed()
 			},
+			(http::Method::GET, ["targets", target, "explain"]) => {
+				Self::handle_explain_target_request(handle, request, target).boxed()
+			},
+
+			// Logins.
+			(http::Method::POST, ["login"]) => {
+				Self::handle_create_login_request(handle, request).boxed()
+			},
+			(http::Method::GET, ["login", id]) => {
+				Self::handle_get_login_request(handle, request, id).boxed()
+			},
+			(http::Method::GET, ["oauth", "github"]) => {
+				Self::handle_finish_login_request(handle, request).boxed()
+			},
 
 			// Users.
 			(http::Method::GET, ["user"]) => Self::handle_get_user_request(handle, request).boxed(),
+			(http::Method::DELETE, ["user", "token"]) => {
+				Self::handle_delete_token_request(handle, request).boxed()
+			},
 
 			(_, _) => future::ok(
 				http::Response::builder()
@@ -847,6 +1005,13 @@ impl tg::Handle for Server {
 		self.try_read_blob_stream(id, arg)
 	}
 
+	fn list_builds(
+		&self,
+		arg: tg::build::list::Arg,
+	) -> impl Future<Output = tg::Result<tg::build::list::Output>> {
+		self.list_builds(arg)
+	}
+
 	fn try_get_build(
 		&self,
 		id: &tg::build::Id,
@@ -1089,6 +1254,32 @@ impl tg::Handle for Server {
 		self.remove_remote(name)
 	}
 
+	fn list_roots(
+		&self,
+		arg: tg::root::list::Arg,
+	) -> impl Future<Output = tg::Result<tg::root::list::Output>> {
+		self.list_roots(arg)
+	}
+
+	fn try_get_root(
+		&self,
+		name: &str,
+	) -> impl Future<Output = tg::Result<Option<tg::root::get::Output>>> {
+		self.try_get_root(name)
+	}
+
+	fn put_root(
+		&self,
+		name: &str,
+		arg: tg::root::put::Arg,
+	) -> impl Future<Output = tg::Result<()>> {
+		self.put_root(name, arg)
+	}
+
+	fn delete_root(&self, name: &str) -> impl Future<Output = tg::Result<()>> {
+		self.delete_root(name)
+	}
+
 	fn get_js_runtime_doc(&self) -> impl Future<Output = tg::Result<serde_json::Value>> {
 		self.get_js_runtime_doc()
 	}
@@ -1097,8 +1288,11 @@ impl tg::Handle for Server {
 		self.health()
 	}
 
-	fn clean(&self) -> impl Future<Output = tg::Result<()>> {
-		self.clean()
+	fn clean(
+		&self,
+		arg: tg::server::clean::Arg,
+	) -> impl Future<Output = tg::Result<tg::server::clean::Output>> {
+		self.clean(arg)
 	}
 
 	fn list_tags(
@@ -1135,9 +1329,37 @@ impl tg::Handle for Server {
 		self.try_build_target(id, arg)
 	}
 
+	fn explain_target(
+		&self,
+		id: &tg::target::Id,
+		arg: tg::target::explain::Arg,
+	) -> impl Future<Output = tg::Result<tg::target::explain::Output>> {
+		self.explain_target(id, arg)
+	}
+
+	fn create_login(&self) -> impl Future<Output = tg::Result<tg::Login>> {
+		self.create_login()
+	}
+
+	fn try_get_login(
+		&self,
+		id: &tg::login::Id,
+		arg: tg::login::get::Arg,
+	) -> impl Future<Output = tg::Result<Option<tg::Login>>> {
+		self.try_get_login(id, arg)
+	}
+
+	fn finish_login(&self, arg: tg::login::finish::Arg) -> impl Future<Output = tg::Result<()>> {
+		self.finish_login(arg)
+	}
+
 	fn get_user(&self, token: &str) -> impl Future<Output = tg::Result<Option<tg::user::User>>> {
 		self.get_user(token)
 	}
+
+	fn delete_token(&self, token: &str) -> impl Future<Output = tg::Result<()>> {
+		self.delete_token(token)
+	}
 }
 
 impl std::ops::Deref for Server {
@@ -1147,3 +1369,99 @@ impl std::ops::Deref for Server {
 		&self.0
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::TestServer;
+
+	/// Create a key and a certificate for a name, signed by a certificate authority if one is given.
+	fn generate(
+		path: &Path,
+		name: &str,
+		purpose: rcgen::ExtendedKeyUsagePurpose,
+		issuer: Option<&(rcgen::Certificate, rcgen::KeyPair)>,
+	) -> (rcgen::Certificate, rcgen::KeyPair) {
+		let key = rcgen::KeyPair::generate().unwrap();
+		let mut params = rcgen::CertificateParams::new(vec![name.to_owned()]).unwrap();
+		let certificate = if let Some((issuer, issuer_key)) = issuer {
+			params.extended_key_usages = vec![purpose];
+			params.signed_by(&key, issuer, issuer_key).unwrap()
+		} else {
+			params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+			params.self_signed(&key).unwrap()
+		};
+		std::fs::write(path.join(format!("{name}.pem")), certificate.pem()).unwrap();
+		std::fs::write(path.join(format!("{name}.key")), key.serialize_pem()).unwrap();
+		(certificate, key)
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn serve_https() {
+		let server = TestServer::new().await;
+		let path = server.temp_path();
+
+		// Create the certificate authorities and the certificates.
+		let client = rcgen::ExtendedKeyUsagePurpose::ClientAuth;
+		let ca = generate(path, "ca", client.clone(), None);
+		let other_ca = generate(path, "other_ca", client.clone(), None);
+		generate(
+			path,
+			"localhost",
+			rcgen::ExtendedKeyUsagePurpose::ServerAuth,
+			Some(&ca),
+		);
+		generate(path, "client", client.clone(), Some(&ca));
+		generate(path, "other_client", client, Some(&other_ca));
+
+		// Serve on an https url that requires client certificates signed by the certificate authority.
+		let port = std::net::TcpListener::bind("localhost:0")
+			.unwrap()
+			.local_addr()
+			.unwrap()
+			.port();
+		let url: Url = format!("https://localhost:{port}").parse().unwrap();
+		let tls = self::options::Tls {
+			certificate: path.join("localhost.pem"),
+			key: path.join("localhost.key"),
+			client_certificate_authorities: Some(path.join("ca.pem")),
+		};
+		let stop = Stop::new();
+		let task = tokio::spawn(Server::serve(
+			(*server).clone(),
+			url.clone(),
+			None,
+			Some(tls),
+			stop.clone(),
+		));
+		while tokio::net::TcpStream::connect(format!("localhost:{port}"))
+			.await
+			.is_err()
+		{
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+
+		// Create a client with the certificate authority and a client certificate.
+		let client = |name: Option<&str>| {
+			let tls = tg::Tls {
+				certificate_authorities: Some(path.join("ca.pem")),
+				certificate: name.map(|name| path.join(format!("{name}.pem"))),
+				key: name.map(|name| path.join(format!("{name}.key"))),
+			};
+			tg::Client::with_tls(url.clone(), None, Some(tls))
+		};
+
+		// A client with a certificate signed by the certificate authority is accepted.
+		client(Some("client")).health().await.unwrap();
+
+		// A client without a certificate is rejected.
+		assert!(client(None).health().await.is_err());
+
+		// A client with a certificate signed by another certificate authority is rejected.
+		assert!(client(Some("other_client")).health().await.is_err());
+
+		// Stop the server.
+		stop.stop();
+		task.await.unwrap().unwrap();
+	}
+}
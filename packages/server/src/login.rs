@@ -0,0 +1,520 @@
+use crate::Server;
+use indoc::formatdoc;
+use std::time::Duration;
+use tangram_client as tg;
+use tangram_database::{self as db, prelude::*};
+use tangram_http::{incoming::request::Ext as _, outgoing::response::Ext as _, Incoming, Outgoing};
+use time::format_description::well_known::Rfc3339;
+
+/// The default URL of GitHub's API.
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// The duration after which an unfinished login expires.
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(600);
+
+type OauthClient = oauth2::basic::BasicClient<
+	oauth2::EndpointSet,
+	oauth2::EndpointNotSet,
+	oauth2::EndpointNotSet,
+	oauth2::EndpointNotSet,
+	oauth2::EndpointSet,
+>;
+
+#[derive(serde::Deserialize)]
+struct GithubEmail {
+	email: String,
+	primary: bool,
+	verified: bool,
+}
+
+impl Server {
+	pub async fn create_login(&self) -> tg::Result<tg::Login> {
+		// Get the OAuth client.
+		let options = self.github_oauth_options()?;
+		let client = Self::github_oauth_client(options)?;
+
+		// Create the login's secret. The login's ID is part of the URL, so the secret is required to get the login's token.
+		let secret = data_encoding::HEXLOWER.encode(&random_bytes()?);
+
+		// Create the authorization URL. The login's ID is used as the state, so that the callback can find the login.
+		let id = tg::login::Id::new();
+		let (url, _) = client
+			.authorize_url(|| oauth2::CsrfToken::new(id.to_string()))
+			.add_scope(oauth2::Scope::new("user:email".to_owned()))
+			.url();
+
+		// Get a database connection.
+		let connection = self
+			.database
+			.connection(db::Priority::Low)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
+
+		// Insert the login.
+		let p = connection.p();
+		let statement = formatdoc!(
+			"
+				insert into logins (id, url, secret, created_at)
+				values ({p}1, {p}2, {p}3, {p}4);
+			"
+		);
+		let created_at = time::OffsetDateTime::now_utc().format(&Rfc3339).unwrap();
+		let params = db::params![id, url, secret, created_at];
+		connection
+			.execute(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+
+		// Drop the database connection.
+		drop(connection);
+
+		let login = tg::Login {
+			id,
+			url,
+			secret: Some(secret),
+			token: None,
+			error: None,
+		};
+
+		Ok(login)
+	}
+
+	/// Get a login with its secret. Once a login has finished with a token or an error, it is returned once and then deleted, so that its token cannot be retrieved again.
+	pub async fn try_get_login(
+		&self,
+		id: &tg::login::Id,
+		arg: tg::login::get::Arg,
+	) -> tg::Result<Option<tg::Login>> {
+		// Get a database connection.
+		let mut connection = self
+			.database
+			.connection(db::Priority::Low)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
+
+		// Begin a transaction.
+		let transaction = connection
+			.transaction()
+			.await
+			.map_err(|source| tg::error!(!source, "failed to begin a transaction"))?;
+
+		// Delete the logins that expired before they were finished.
+		let p = transaction.p();
+		let statement = formatdoc!(
+			"
+				delete from logins
+				where token is null and error is null and created_at < {p}1;
+			"
+		);
+		let params = db::params![Self::login_min_created_at()];
+		transaction
+			.execute(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+
+		// Get the login.
+		let statement = formatdoc!(
+			"
+				select id, url, token, error
+				from logins
+				where id = {p}1 and secret = {p}2;
+			"
+		);
+		let params = db::params![id, arg.secret];
+		let login = transaction
+			.query_optional_into::<tg::Login>(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+
+		// If the login is finished, then delete it.
+		if login
+			.as_ref()
+			.is_some_and(|login| login.token.is_some() || login.error.is_some())
+		{
+			let statement = formatdoc!(
+				"
+					delete from logins
+					where id = {p}1;
+				"
+			);
+			let params = db::params![id];
+			transaction
+				.execute(statement, params)
+				.await
+				.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+		}
+
+		// Commit the transaction.
+		transaction
+			.commit()
+			.await
+			.map_err(|source| tg::error!(!source, "failed to commit the transaction"))?;
+
+		// Drop the database connection.
+		drop(connection);
+
+		Ok(login)
+	}
+
+	pub async fn finish_login(&self, arg: tg::login::finish::Arg) -> tg::Result<()> {
+		// Finish the login. If this fails, then store the error on the login, so that the client that is waiting for it can report the error.
+		let id = arg.state.clone();
+		let result = self.finish_login_inner(arg).await;
+		if let Err(error) = &result {
+			self.set_login_error(&id, error)
+				.await
+				.inspect_err(|error| tracing::error!(?error, "failed to set the login's error"))
+				.ok();
+		}
+		result
+	}
+
+	async fn finish_login_inner(&self, arg: tg::login::finish::Arg) -> tg::Result<()> {
+		// Get the OAuth client.
+		let options = self.github_oauth_options()?;
+		let client = Self::github_oauth_client(options)?;
+
+		// Get a database connection.
+		let connection = self
+			.database
+			.connection(db::Priority::Low)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
+
+		// Ensure the login exists, has not expired, and has not been finished.
+		let p = connection.p();
+		let statement = formatdoc!(
+			"
+				select count(*) != 0
+				from logins
+				where id = {p}1 and token is null and error is null and created_at >= {p}2;
+			"
+		);
+		let params = db::params![arg.state, Self::login_min_created_at()];
+		let exists = connection
+			.query_one_value_into::<bool>(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+		if !exists {
+			return Err(tg::error!(
+				"the login does not exist, has expired, or has already been finished"
+			));
+		}
+
+		// Drop the database connection.
+		drop(connection);
+
+		// Exchange the code for an access token. The HTTP client must not follow redirects.
+		let http_client = reqwest::Client::builder()
+			.redirect(reqwest::redirect::Policy::none())
+			.build()
+			.map_err(|source| tg::error!(!source, "failed to create the HTTP client"))?;
+		let response = client
+			.exchange_code(oauth2::AuthorizationCode::new(arg.code))
+			.request_async(&http_client)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to exchange the code"))?;
+		let access_token = oauth2::TokenResponse::access_token(&response)
+			.secret()
+			.clone();
+
+		// Get the user's primary verified email from the provider's API.
+		let api_url = options.api_url.as_deref().unwrap_or(GITHUB_API_URL);
+		let octocrab = octocrab::Octocrab::builder()
+			.base_uri(api_url)
+			.map_err(|source| tg::error!(!source, %api_url, "invalid API url"))?
+			.user_access_token(access_token)
+			.build()
+			.map_err(|source| tg::error!(!source, "failed to create the GitHub client"))?;
+		let emails: Vec<GithubEmail> = octocrab
+			.get("/user/emails", None::<&()>)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to get the user's emails"))?;
+		let email = emails
+			.into_iter()
+			.find(|email| email.primary && email.verified)
+			.ok_or_else(|| tg::error!("the user does not have a primary verified email"))?
+			.email;
+
+		// Get a database connection.
+		let mut connection = self
+			.database
+			.connection(db::Priority::Low)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
+
+		// Begin a transaction, so that the user and token are only created if the login is finished.
+		let transaction = connection
+			.transaction()
+			.await
+			.map_err(|source| tg::error!(!source, "failed to begin a transaction"))?;
+
+		// Get the user with the email, or create one.
+		let p = transaction.p();
+		let statement = formatdoc!(
+			"
+				select id
+				from users
+				where email = {p}1;
+			"
+		);
+		let params = db::params![email];
+		let user = transaction
+			.query_optional_value_into::<tg::user::Id>(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+		let user = if let Some(user) = user {
+			user
+		} else {
+			let user = tg::user::Id::new();
+			let statement = formatdoc!(
+				"
+					insert into users (id, email)
+					values ({p}1, {p}2);
+				"
+			);
+			let params = db::params![user, email];
+			transaction
+				.execute(statement, params)
+				.await
+				.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+			user
+		};
+
+		// Create a token for the user. The token is random, unlike an ID, so that it cannot be guessed.
+		let token = tg::Id::new_blake3(tg::id::Kind::Token, &random_bytes()?);
+		let statement = formatdoc!(
+			r#"
+				insert into tokens (id, "user")
+				values ({p}1, {p}2);
+			"#
+		);
+		let params = db::params![token, user];
+		transaction
+			.execute(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+
+		// Set the login's token if the login has not been finished concurrently.
+		let statement = formatdoc!(
+			"
+				update logins
+				set token = {p}1
+				where id = {p}2 and token is null and error is null;
+			"
+		);
+		let params = db::params![token, arg.state];
+		let n = transaction
+			.execute(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+		if n == 0 {
+			return Err(tg::error!("the login has already been finished"));
+		}
+
+		// Commit the transaction.
+		transaction
+			.commit()
+			.await
+			.map_err(|source| tg::error!(!source, "failed to commit the transaction"))?;
+
+		// Drop the database connection.
+		drop(connection);
+
+		Ok(())
+	}
+
+	async fn set_login_error(&self, id: &tg::login::Id, error: &tg::Error) -> tg::Result<()> {
+		// Get a database connection.
+		let connection = self
+			.database
+			.connection(db::Priority::Low)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
+
+		// Set the login's error if it has not been finished.
+		let p = connection.p();
+		let statement = formatdoc!(
+			"
+				update logins
+				set error = {p}1
+				where id = {p}2 and token is null and error is null;
+			"
+		);
+		let params = db::params![error, id];
+		connection
+			.execute(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+
+		// Drop the database connection.
+		drop(connection);
+
+		Ok(())
+	}
+
+	/// Get the time before which unfinished logins are expired.
+	fn login_min_created_at() -> String {
+		let min_created_at = time::OffsetDateTime::now_utc() - LOGIN_TIMEOUT;
+		min_created_at.format(&Rfc3339).unwrap()
+	}
+
+	fn github_oauth_options(&self) -> tg::Result<&crate::options::Oauth> {
+		self.options
+			.authentication
+			.providers
+			.as_ref()
+			.and_then(|providers| providers.github.as_ref())
+			.ok_or_else(|| tg::error!("the GitHub authentication provider is not configured"))
+	}
+
+	fn github_oauth_client(options: &crate::options::Oauth) -> tg::Result<OauthClient> {
+		let auth_url = oauth2::AuthUrl::new(options.auth_url.clone())
+			.map_err(|source| tg::error!(!source, "invalid auth url"))?;
+		let token_url = oauth2::TokenUrl::new(options.token_url.clone())
+			.map_err(|source| tg::error!(!source, "invalid token url"))?;
+		let redirect_url = oauth2::RedirectUrl::new(options.redirect_url.clone())
+			.map_err(|source| tg::error!(!source, "invalid redirect url"))?;
+		let client =
+			oauth2::basic::BasicClient::new(oauth2::ClientId::new(options.client_id.clone()))
+				.set_client_secret(oauth2::ClientSecret::new(options.client_secret.clone()))
+				.set_auth_uri(auth_url)
+				.set_token_uri(token_url)
+				.set_redirect_uri(redirect_url);
+		Ok(client)
+	}
+}
+
+/// Get 32 random bytes for a login's secret or token.
+fn random_bytes() -> tg::Result<[u8; 32]> {
+	let mut bytes = [0; 32];
+	getrandom::getrandom(&mut bytes)
+		.map_err(|source| tg::error!(!source, "failed to get random bytes"))?;
+	Ok(bytes)
+}
+
+impl Server {
+	pub(crate) async fn handle_create_login_request<H>(
+		handle: &H,
+		_request: http::Request<Incoming>,
+	) -> tg::Result<http::Response<Outgoing>>
+	where
+		H: tg::Handle,
+	{
+		let output = handle.create_login().await?;
+		let response = http::Response::builder().json(output).unwrap();
+		Ok(response)
+	}
+
+	pub(crate) async fn handle_get_login_request<H>(
+		handle: &H,
+		request: http::Request<Incoming>,
+		id: &str,
+	) -> tg::Result<http::Response<Outgoing>>
+	where
+		H: tg::Handle,
+	{
+		let id = id.parse()?;
+		let arg = request
+			.query_params()
+			.transpose()?
+			.ok_or_else(|| tg::error!("query parameters required"))?;
+		let Some(output) = handle.try_get_login(&id, arg).await? else {
+			return Ok(http::Response::builder().not_found().empty().unwrap());
+		};
+		let response = http::Response::builder().json(output).unwrap();
+		Ok(response)
+	}
+
+	pub(crate) async fn handle_finish_login_request<H>(
+		handle: &H,
+		request: http::Request<Incoming>,
+	) -> tg::Result<http::Response<Outgoing>>
+	where
+		H: tg::Handle,
+	{
+		let arg = request
+			.query_params()
+			.transpose()?
+			.ok_or_else(|| tg::error!("query parameters required"))?;
+		handle.finish_login(arg).await?;
+		let response = http::Response::builder()
+			.bytes("You have been logged in. You may now close this window.")
+			.unwrap();
+		Ok(response)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::TestServer;
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn failed_login() {
+		let server = TestServer::with_options(|options| {
+			options.authentication.providers = Some(crate::options::AuthenticationProviders {
+				github: Some(crate::options::Oauth {
+					api_url: None,
+					auth_url: "http://localhost:1/authorize".to_owned(),
+					client_id: "client".to_owned(),
+					client_secret: "secret".to_owned(),
+					redirect_url: "http://localhost:1/oauth/github".to_owned(),
+					token_url: "http://localhost:1/token".to_owned(),
+				}),
+			});
+		})
+		.await;
+
+		// An unfinished login can be retrieved repeatedly, but only with its secret.
+		let login = server.create_login().await.unwrap();
+		let secret = login.secret.clone().unwrap();
+		assert!(!login.url.as_str().contains(&secret));
+		let arg = tg::login::get::Arg { secret };
+		let output = server
+			.try_get_login(&login.id, arg.clone())
+			.await
+			.unwrap()
+			.unwrap();
+		assert!(output.secret.is_none() && output.token.is_none() && output.error.is_none());
+		assert!(server
+			.try_get_login(&login.id, arg.clone())
+			.await
+			.unwrap()
+			.is_some());
+		let wrong = tg::login::get::Arg {
+			secret: "secret".to_owned(),
+		};
+		assert!(server
+			.try_get_login(&login.id, wrong)
+			.await
+			.unwrap()
+			.is_none());
+
+		// If the login fails, then its error is stored and it is returned once.
+		let finish = tg::login::finish::Arg {
+			code: "code".to_owned(),
+			state: login.id.clone(),
+		};
+		assert!(server.finish_login(finish.clone()).await.is_err());
+		let output = server
+			.try_get_login(&login.id, arg.clone())
+			.await
+			.unwrap()
+			.unwrap();
+		assert!(output.token.is_none() && output.error.is_some());
+		assert!(server
+			.try_get_login(&login.id, arg.clone())
+			.await
+			.unwrap()
+			.is_none());
+
+		// A login that does not exist cannot be finished.
+		assert!(server.finish_login(finish).await.is_err());
+		assert!(server
+			.try_get_login(&login.id, arg)
+			.await
+			.unwrap()
+			.is_none());
+	}
+}
@@ -6,7 +6,17 @@ use tangram_client as tg;
 
 impl Server {
 	pub(crate) async fn migrate(path: &Path) -> tg::Result<()> {
-		let migrations = vec![migration_0000(path).boxed()];
+		let migrations = vec![
+			migration_0000(path).boxed(),
+			migration_0001(path).boxed(),
+			migration_0002(path).boxed(),
+			migration_0003(path).boxed(),
+			migration_0004(path).boxed(),
+			migration_0005(path).boxed(),
+			migration_0006(path).boxed(),
+			migration_0007(path).boxed(),
+			migration_0008(path).boxed(),
+		];
 
 		// Read the version from the version file.
 		let version = match tokio::fs::read_to_string(path.join("version")).await {
@@ -181,3 +191,161 @@ async fn migration_0000(path: &Path) -> tg::Result<()> {
 		.map_err(|source| tg::error!(!source, "failed to create the database tables"))?;
 	Ok(())
 }
+
+async fn migration_0001(path: &Path) -> tg::Result<()> {
+	let path = path.to_owned();
+	let connection = rusqlite::Connection::open(path.join("database"))
+		.map_err(|source| tg::error!(!source, "failed to open the database"))?;
+	let sql = formatdoc!(
+		"
+			create table roots (
+				name text primary key,
+				item text not null
+			);
+
+			create index roots_item_index on roots (item);
+		"
+	);
+	connection
+		.execute_batch(&sql)
+		.map_err(|source| tg::error!(!source, "failed to create the roots table"))?;
+	Ok(())
+}
+
+async fn migration_0002(path: &Path) -> tg::Result<()> {
+	let path = path.to_owned();
+	let connection = rusqlite::Connection::open(path.join("database"))
+		.map_err(|source| tg::error!(!source, "failed to open the database"))?;
+	let sql = formatdoc!(
+		"
+			alter table builds add column timeout real;
+		"
+	);
+	connection
+		.execute_batch(&sql)
+		.map_err(|source| tg::error!(!source, "failed to add the timeout column"))?;
+	Ok(())
+}
+
+async fn migration_0003(path: &Path) -> tg::Result<()> {
+	let path = path.to_owned();
+	let connection = rusqlite::Connection::open(path.join("database"))
+		.map_err(|source| tg::error!(!source, "failed to open the database"))?;
+	let sql = formatdoc!(
+		"
+			create table build_labels (
+				build text not null,
+				label text not null
+			);
+
+			create unique index build_labels_index on build_labels (build, label);
+		"
+	);
+	connection
+		.execute_batch(&sql)
+		.map_err(|source| tg::error!(!source, "failed to create the build labels table"))?;
+	Ok(())
+}
+
+async fn migration_0004(path: &Path) -> tg::Result<()> {
+	let path = path.to_owned();
+	let connection = rusqlite::Connection::open(path.join("database"))
+		.map_err(|source| tg::error!(!source, "failed to open the database"))?;
+	let sql = formatdoc!(
+		"
+			alter table builds add column priority integer not null default 0;
+
+			alter table builds add column root text;
+
+			create index builds_status_priority_index on builds (status, priority desc, created_at);
+
+			create index builds_root_status_index on builds (root, status);
+		"
+	);
+	connection.execute_batch(&sql).map_err(|source| {
+		tg::error!(!source, "failed to add the build priority and root columns")
+	})?;
+	Ok(())
+}
+
+async fn migration_0005(path: &Path) -> tg::Result<()> {
+	let path = path.to_owned();
+	let connection = rusqlite::Connection::open(path.join("database"))
+		.map_err(|source| tg::error!(!source, "failed to open the database"))?;
+	let sql = formatdoc!(
+		"
+			alter table builds add column outcome_kind text;
+
+			update builds
+			set outcome_kind = json_extract(outcome, '$.kind')
+			where outcome is not null;
+
+			create index builds_outcome_kind_index on builds (outcome_kind);
+		"
+	);
+	connection
+		.execute_batch(&sql)
+		.map_err(|source| tg::error!(!source, "failed to add the outcome kind column"))?;
+	Ok(())
+}
+
+/// Add the token scopes column. New tokens default to the read and write scopes, and the existing tokens, which could build before tokens had scopes, keep the build scope.
+async fn migration_0006(path: &Path) -> tg::Result<()> {
+	let path = path.to_owned();
+	let connection = rusqlite::Connection::open(path.join("database"))
+		.map_err(|source| tg::error!(!source, "failed to open the database"))?;
+	let sql = formatdoc!(
+		"
+			alter table tokens add column scopes text not null default 'read write';
+
+			update tokens set scopes = 'read write build';
+		"
+	);
+	connection
+		.execute_batch(&sql)
+		.map_err(|source| tg::error!(!source, "failed to add the token scopes column"))?;
+	Ok(())
+}
+
+async fn migration_0007(path: &Path) -> tg::Result<()> {
+	let path = path.to_owned();
+	let connection = rusqlite::Connection::open(path.join("database"))
+		.map_err(|source| tg::error!(!source, "failed to open the database"))?;
+	let sql = formatdoc!(
+		"
+			create table logins (
+				id text primary key,
+				url text not null,
+				secret text not null,
+				token text,
+				error text,
+				created_at text not null
+			);
+
+			create unique index users_email_index on users (email);
+		"
+	);
+	connection
+		.execute_batch(&sql)
+		.map_err(|source| tg::error!(!source, "failed to create the logins table"))?;
+	Ok(())
+}
+
+async fn migration_0008(path: &Path) -> tg::Result<()> {
+	let path = path.to_owned();
+	let connection = rusqlite::Connection::open(path.join("database"))
+		.map_err(|source| tg::error!(!source, "failed to open the database"))?;
+	let sql = formatdoc!(
+		"
+			create table remotes (
+				name text primary key,
+				url text not null,
+				token text
+			);
+		"
+	);
+	connection
+		.execute_batch(&sql)
+		.map_err(|source| tg::error!(!source, "failed to create the remotes table"))?;
+	Ok(())
+}
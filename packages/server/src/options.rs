@@ -1,4 +1,8 @@
-use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	path::PathBuf,
+	time::Duration,
+};
 use tangram_client as tg;
 use url::Url;
 
@@ -6,6 +10,7 @@ use url::Url;
 pub struct Options {
 	pub advanced: Advanced,
 	pub authentication: Authentication,
+	pub authorization: Option<Authorization>,
 	pub build: Option<Build>,
 	pub build_heartbeat_monitor: Option<BuildHeartbeatMonitor>,
 	pub build_indexer: Option<BuildIndexer>,
@@ -14,6 +19,8 @@ pub struct Options {
 	pub object_indexer: Option<ObjectIndexer>,
 	pub path: PathBuf,
 	pub remotes: BTreeMap<String, Remote>,
+	pub runtimes: BTreeMap<String, Runtime>,
+	pub tls: Option<Tls>,
 	pub url: Url,
 	pub version: Option<String>,
 	pub vfs: Option<Vfs>,
@@ -39,8 +46,14 @@ pub struct AuthenticationProviders {
 	pub github: Option<Oauth>,
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct Authorization {
+	pub anonymous: BTreeSet<tg::user::Scope>,
+}
+
 #[derive(Clone, Debug)]
 pub struct Oauth {
+	pub api_url: Option<String>,
 	pub auth_url: String,
 	pub client_id: String,
 	pub client_secret: String,
@@ -52,6 +65,24 @@ pub struct Oauth {
 pub struct Build {
 	pub concurrency: usize,
 	pub heartbeat_interval: Duration,
+	pub labels: BTreeSet<String>,
+	pub limits: BuildLimits,
+	pub mounts: Vec<BuildMount>,
+	pub timeout: Option<Duration>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BuildLimits {
+	pub cpu: Option<f64>,
+	pub memory: Option<u64>,
+	pub pids: Option<u64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BuildMount {
+	pub source: PathBuf,
+	pub target: PathBuf,
+	pub readonly: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -111,9 +142,57 @@ pub struct Remote {
 	pub client: tg::Client,
 }
 
+#[derive(Clone, Debug)]
+pub struct Runtime {
+	pub executable: PathBuf,
+	pub args: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Tls {
+	pub certificate: PathBuf,
+	pub key: PathBuf,
+	pub client_certificate_authorities: Option<PathBuf>,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Vfs {
 	pub cache_ttl: f64,
 	pub cache_size: u64,
 	pub database_connections: usize,
 }
+
+#[cfg(test)]
+impl Options {
+	/// Create the options for a test server in a directory.
+	pub(crate) fn test(path: PathBuf) -> Self {
+		let socket = path.join("socket");
+		let socket = urlencoding::encode(socket.to_str().unwrap());
+		let url = format!("http+unix://{socket}").parse().unwrap();
+		Self {
+			advanced: Advanced {
+				build_dequeue_timeout: None,
+				error_trace_options: tg::error::TraceOptions::default(),
+				file_descriptor_semaphore_size: 1024,
+				preserve_temp_directories: false,
+				write_build_logs_to_database: false,
+				write_build_logs_to_stderr: false,
+			},
+			authentication: Authentication::default(),
+			authorization: None,
+			build: None,
+			build_heartbeat_monitor: None,
+			build_indexer: None,
+			database: Database::Sqlite(SqliteDatabase { connections: 1 }),
+			messenger: Messenger::Memory,
+			object_indexer: None,
+			path,
+			remotes: BTreeMap::new(),
+			runtimes: BTreeMap::new(),
+			tls: None,
+			url,
+			version: None,
+			vfs: None,
+		}
+	}
+}
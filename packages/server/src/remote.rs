@@ -1,4 +1,83 @@
+use crate::Server;
+use indoc::formatdoc;
+use std::collections::BTreeSet;
+use tangram_client as tg;
+use tangram_database::{self as db, prelude::*};
+use url::Url;
+
 pub mod delete;
 pub mod get;
 pub mod list;
 pub mod put;
+
+impl Server {
+	/// Add the remotes stored in the database. These take precedence over remotes with the same name in the options.
+	pub(crate) async fn load_remotes(&self) -> tg::Result<()> {
+		// Get a database connection.
+		let connection = self
+			.database
+			.connection(db::Priority::Low)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
+
+		// Get the remotes.
+		#[derive(serde::Deserialize)]
+		struct Row {
+			name: String,
+			url: Url,
+			token: Option<String>,
+		}
+		let statement = formatdoc!(
+			"
+				select name, url, token
+				from remotes;
+			"
+		);
+		let params = db::params![];
+		let rows = connection
+			.query_all_into::<Row>(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+
+		// Drop the database connection.
+		drop(connection);
+
+		// Add the remotes.
+		for row in rows {
+			let client = tg::Client::with_token(row.url, row.token);
+			self.remotes.insert(row.name, client);
+		}
+
+		Ok(())
+	}
+
+	/// Get the names of the remotes stored in the database.
+	pub(crate) async fn get_database_remote_names(&self) -> tg::Result<BTreeSet<String>> {
+		// Get a database connection.
+		let connection = self
+			.database
+			.connection(db::Priority::Low)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
+
+		// Get the names.
+		let statement = formatdoc!(
+			"
+				select name
+				from remotes;
+			"
+		);
+		let params = db::params![];
+		let names = connection
+			.query_all_value_into(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?
+			.into_iter()
+			.collect();
+
+		// Drop the database connection.
+		drop(connection);
+
+		Ok(names)
+	}
+}
@@ -1,12 +1,54 @@
 use crate::Server;
+use indoc::formatdoc;
 use tangram_client as tg;
+use tangram_database::{self as db, prelude::*};
 use tangram_http::{outgoing::response::Ext as _, Incoming, Outgoing};
 
 impl Server {
 	pub async fn remove_remote(&self, name: &str) -> tg::Result<()> {
-		self.remotes
-			.remove(name)
-			.ok_or_else(|| tg::error!("failed to find the remote"))?;
+		// Get a database connection.
+		let connection = self
+			.database
+			.connection(db::Priority::Low)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
+
+		// Delete the remote.
+		let p = connection.p();
+		let statement = formatdoc!(
+			"
+				delete from remotes
+				where name = {p}1;
+			"
+		);
+		let params = db::params![name];
+		let n = connection
+			.execute(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+
+		// Drop the database connection.
+		drop(connection);
+
+		// If the remote was not in the database, then it cannot be removed.
+		let config = self.options.remotes.get(name);
+		if n == 0 {
+			if config.is_some() {
+				return Err(tg::error!(
+					%name,
+					"the remote is configured by the server's config and cannot be removed"
+				));
+			}
+			return Err(tg::error!(%name, "failed to find the remote"));
+		}
+
+		// Remove the remote, or restore the remote from the config that it shadowed.
+		if let Some(config) = config {
+			self.remotes.insert(name.to_owned(), config.client.clone());
+		} else {
+			self.remotes.remove(name);
+		}
+
 		Ok(())
 	}
 }
@@ -25,3 +67,48 @@ impl Server {
 		Ok(response)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::TestServer;
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn remove_remote() {
+		let config_url = "http://localhost:1".parse::<url::Url>().unwrap();
+		let server = TestServer::with_options(|options| {
+			let remote = crate::options::Remote {
+				build: false,
+				client: tg::Client::with_token(config_url.clone(), None),
+			};
+			options.remotes.insert("config".to_owned(), remote);
+		})
+		.await;
+		let url = |name: &str| server.remotes.get(name).map(|remote| remote.url().clone());
+
+		// A remote that is only in the config cannot be removed.
+		assert!(server.remove_remote("config").await.is_err());
+		assert_eq!(url("config"), Some(config_url.clone()));
+
+		// Removing a remote that shadows a remote from the config restores the remote from the config.
+		let database_url = "http://localhost:2".parse::<url::Url>().unwrap();
+		let arg = tg::remote::put::Arg {
+			url: database_url.clone(),
+			token: None,
+		};
+		server.put_remote("config", arg).await.unwrap();
+		assert_eq!(url("config"), Some(database_url.clone()));
+		server.remove_remote("config").await.unwrap();
+		assert_eq!(url("config"), Some(config_url));
+
+		// Removing a remote that is only in the database removes it.
+		let arg = tg::remote::put::Arg {
+			url: database_url,
+			token: None,
+		};
+		server.put_remote("database", arg).await.unwrap();
+		server.remove_remote("database").await.unwrap();
+		assert_eq!(url("database"), None);
+		assert!(server.remove_remote("database").await.is_err());
+	}
+}
@@ -9,7 +9,17 @@ impl Server {
 			return Ok(None);
 		};
 		let url = client.url().clone();
-		let output = tg::remote::get::Output { name, url };
+		drop(client);
+		let source = if self.get_database_remote_names().await?.contains(&name) {
+			tg::remote::Source::Database
+		} else {
+			tg::remote::Source::Config
+		};
+		let output = tg::remote::get::Output {
+			name,
+			source: Some(source),
+			url,
+		};
 		Ok(Some(output))
 	}
 }
@@ -7,13 +7,23 @@ impl Server {
 		&self,
 		_arg: tg::remote::list::Arg,
 	) -> tg::Result<tg::remote::list::Output> {
+		let database_remote_names = self.get_database_remote_names().await?;
 		let items = self
 			.remotes
 			.iter()
 			.map(|entry| {
 				let name = entry.key().to_owned();
+				let source = if database_remote_names.contains(&name) {
+					tg::remote::Source::Database
+				} else {
+					tg::remote::Source::Config
+				};
 				let url = entry.value().url().clone();
-				tg::remote::get::Output { name, url }
+				tg::remote::get::Output {
+					name,
+					source: Some(source),
+					url,
+				}
 			})
 			.collect();
 		let output = tg::remote::list::Output { data: items };
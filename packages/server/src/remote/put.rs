@@ -1,11 +1,40 @@
 use crate::Server;
+use indoc::formatdoc;
 use tangram_client as tg;
+use tangram_database::{self as db, prelude::*};
 use tangram_http::{incoming::request::Ext as _, outgoing::response::Ext as _, Incoming, Outgoing};
 
 impl Server {
 	pub async fn put_remote(&self, name: &str, arg: tg::remote::put::Arg) -> tg::Result<()> {
-		let remote = tg::Client::new(arg.url);
+		// Get a database connection.
+		let connection = self
+			.database
+			.connection(db::Priority::Low)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
+
+		// Insert the remote.
+		let p = connection.p();
+		let statement = formatdoc!(
+			"
+				insert into remotes (name, url, token)
+				values ({p}1, {p}2, {p}3)
+				on conflict (name) do update set url = {p}2, token = {p}3;
+			"
+		);
+		let params = db::params![name, arg.url, arg.token];
+		connection
+			.execute(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+
+		// Drop the database connection.
+		drop(connection);
+
+		// Add the remote.
+		let remote = tg::Client::with_token(arg.url, arg.token);
 		self.remotes.insert(name.to_owned(), remote);
+
 		Ok(())
 	}
 }
@@ -0,0 +1,50 @@
+use crate::Server;
+use indoc::formatdoc;
+use tangram_client as tg;
+use tangram_database::{self as db, prelude::*};
+use tangram_http::{outgoing::response::Ext as _, Incoming, Outgoing};
+
+impl Server {
+	pub async fn delete_root(&self, name: &str) -> tg::Result<()> {
+		// Get a database connection.
+		let connection = self
+			.database
+			.connection(db::Priority::Low)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
+
+		// Delete the root.
+		let p = connection.p();
+		let statement = formatdoc!(
+			"
+				delete from roots
+				where name = {p}1;
+			"
+		);
+		let params = db::params![name];
+		let n = connection
+			.execute(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+		if n == 0 {
+			return Err(tg::error!(%name, "failed to find the root"));
+		}
+
+		Ok(())
+	}
+}
+
+impl Server {
+	pub(crate) async fn handle_delete_root_request<H>(
+		handle: &H,
+		_request: http::Request<Incoming>,
+		name: &str,
+	) -> tg::Result<http::Response<Outgoing>>
+	where
+		H: tg::Handle,
+	{
+		handle.delete_root(name).await?;
+		let response = http::Response::builder().empty().unwrap();
+		Ok(response)
+	}
+}
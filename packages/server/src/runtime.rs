@@ -8,18 +8,22 @@ mod util;
 pub mod builtin;
 #[cfg(target_os = "macos")]
 pub mod darwin;
+pub mod external;
 pub mod js;
 #[cfg(target_os = "linux")]
 pub mod linux;
+pub mod wasm;
 
 #[derive(Clone)]
 pub enum Runtime {
 	Builtin(builtin::Runtime),
 	#[cfg(target_os = "macos")]
 	Darwin(darwin::Runtime),
+	External(external::Runtime),
 	Js(js::Runtime),
 	#[cfg(target_os = "linux")]
 	Linux(linux::Runtime),
+	Wasm(wasm::Runtime),
 }
 
 impl Runtime {
@@ -28,9 +32,11 @@ impl Runtime {
 			Runtime::Builtin(runtime) => runtime.build(build, remote).await,
 			#[cfg(target_os = "macos")]
 			Runtime::Darwin(runtime) => runtime.build(build, remote).await,
+			Runtime::External(runtime) => runtime.build(build, remote).await,
 			Runtime::Js(runtime) => runtime.build(build, remote).await,
 			#[cfg(target_os = "linux")]
 			Runtime::Linux(runtime) => runtime.build(build, remote).await,
+			Runtime::Wasm(runtime) => runtime.build(build, remote).await,
 		}
 	}
 }
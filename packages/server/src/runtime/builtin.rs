@@ -8,7 +8,9 @@ mod checksum;
 mod compress;
 mod decompress;
 mod download;
+mod export_image;
 mod extract;
+mod import_image;
 
 #[derive(Clone)]
 pub struct Runtime {
@@ -46,7 +48,9 @@ impl Runtime {
 			"compress" => self.compress(build, remote).boxed(),
 			"decompress" => self.decompress(build, remote).boxed(),
 			"download" => self.download(build, remote).boxed(),
+			"export_image" => self.export_image(build, remote).boxed(),
 			"extract" => self.extract(build, remote).boxed(),
+			"import_image" => self.import_image(build, remote).boxed(),
 			_ => {
 				return Err(tg::error!("unknown name"));
 			},
@@ -1,5 +1,20 @@
 use super::Runtime;
+use crate::tmp::Tmp;
 use tangram_client as tg;
+use tokio_util::io::SyncIoBridge;
+
+/// An entry in an archive, in the order it will be written.
+enum Entry<C = tg::Blob> {
+	Directory,
+	File {
+		contents: C,
+		executable: bool,
+		size: u64,
+	},
+	Symlink {
+		target: String,
+	},
+}
 
 impl Runtime {
 	pub async fn archive(
@@ -16,7 +31,7 @@ impl Runtime {
 		let args = target.args(server).await?;
 
 		// Get the artifact.
-		let _artifact: tg::Artifact = args
+		let artifact: tg::Artifact = args
 			.get(1)
 			.ok_or_else(|| tg::error!("invalid number of arguments"))?
 			.clone()
@@ -25,7 +40,7 @@ impl Runtime {
 			.ok_or_else(|| tg::error!("expected an artifact"))?;
 
 		// Get the format.
-		let _format = args
+		let format = args
 			.get(2)
 			.ok_or_else(|| tg::error!("invalid number of arguments"))?
 			.try_unwrap_string_ref()
@@ -34,6 +49,289 @@ impl Runtime {
 			.parse::<tg::artifact::archive::Format>()
 			.map_err(|source| tg::error!(!source, "invalid format"))?;
 
-		Err(tg::error!("unimplemented"))
+		// Only directories can be archived, so that the archive extracts to the same artifact.
+		let directory = artifact
+			.try_unwrap_directory()
+			.ok()
+			.ok_or_else(|| tg::error!("the artifact must be a directory"))?;
+
+		// Archive the directory.
+		let blob = self.archive_directory(&directory, format).await?;
+
+		Ok(blob.into())
+	}
+
+	/// Archive a directory to a blob with the given format.
+	pub(super) async fn archive_directory(
+		&self,
+		directory: &tg::Directory,
+		format: tg::artifact::archive::Format,
+	) -> tg::Result<tg::Blob> {
+		let server = &self.server;
+
+		// Collect the entries.
+		let mut entries = Vec::new();
+		self.collect_archive_entries(directory, None, &mut entries)
+			.await?;
+
+		// Create a temporary path.
+		let tmp = Tmp::new(server);
+		let path = tmp.as_ref().to_owned();
+
+		// Write the archive in a blocking task.
+		tokio::task::spawn_blocking({
+			let server = server.clone();
+			let path = path.clone();
+			move || {
+				let file = std::fs::File::create(&path)
+					.map_err(|source| tg::error!(!source, "failed to create the archive file"))?;
+				let open = |blob: &tg::Blob| blob_reader(&server, blob);
+				match format {
+					tg::artifact::archive::Format::Tar => write_tar(file, entries, open),
+					tg::artifact::archive::Format::Zip => write_zip(file, entries, open),
+				}
+			}
+		})
+		.await
+		.unwrap()?;
+
+		// Create the blob.
+		let file = tokio::fs::File::open(&path)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to open the archive file"))?;
+		let blob = tg::Blob::with_reader(server, file).await?;
+
+		Ok(blob)
+	}
+
+	async fn collect_archive_entries(
+		&self,
+		directory: &tg::Directory,
+		path: Option<&str>,
+		entries: &mut Vec<(String, Entry)>,
+	) -> tg::Result<()> {
+		let server = &self.server;
+		for (name, artifact) in directory.entries(server).await? {
+			let path = match path {
+				Some(path) => format!("{path}/{name}"),
+				None => name,
+			};
+			match artifact {
+				tg::Artifact::Directory(directory) => {
+					entries.push((path.clone(), Entry::Directory));
+					Box::pin(self.collect_archive_entries(&directory, Some(&path), entries))
+						.await?;
+				},
+				tg::Artifact::File(file) => {
+					let contents = file.contents(server).await?;
+					let executable = file.executable(server).await?;
+					let size = contents.size(server).await?;
+					let entry = Entry::File {
+						contents,
+						executable,
+						size,
+					};
+					entries.push((path, entry));
+				},
+				tg::Artifact::Symlink(symlink) => {
+					if symlink.artifact(server).await?.is_some() {
+						return Err(
+							tg::error!(%path, "cannot archive a symlink with an artifact, bundle the artifact first"),
+						);
+					}
+					let target = symlink
+						.path(server)
+						.await?
+						.ok_or_else(|| tg::error!(%path, "expected the symlink to have a path"))?;
+					entries.push((path, Entry::Symlink { target }));
+				},
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Write a tar archive, using `open` to read the contents of each file.
+fn write_tar<C, R>(
+	file: impl std::io::Write,
+	entries: Vec<(String, Entry<C>)>,
+	open: impl Fn(&C) -> tg::Result<R>,
+) -> tg::Result<()>
+where
+	R: std::io::Read,
+{
+	let mut builder = tar::Builder::new(file);
+	for (path, entry) in entries {
+		let mut header = tar::Header::new_gnu();
+		header.set_mtime(0);
+		header.set_uid(0);
+		header.set_gid(0);
+		match entry {
+			Entry::Directory => {
+				header.set_entry_type(tar::EntryType::Directory);
+				header.set_mode(0o755);
+				header.set_size(0);
+				builder
+					.append_data(&mut header, &path, std::io::empty())
+					.map_err(
+						|source| tg::error!(!source, %path, "failed to append the directory"),
+					)?;
+			},
+			Entry::File {
+				contents,
+				executable,
+				size,
+			} => {
+				header.set_entry_type(tar::EntryType::Regular);
+				header.set_mode(if executable { 0o755 } else { 0o644 });
+				header.set_size(size);
+				let reader = open(&contents)?;
+				builder
+					.append_data(&mut header, &path, reader)
+					.map_err(|source| tg::error!(!source, %path, "failed to append the file"))?;
+			},
+			Entry::Symlink { target } => {
+				header.set_entry_type(tar::EntryType::Symlink);
+				header.set_mode(0o777);
+				header.set_size(0);
+				builder
+					.append_link(&mut header, &path, &target)
+					.map_err(|source| tg::error!(!source, %path, "failed to append the symlink"))?;
+			},
+		}
+	}
+	let mut file = builder
+		.into_inner()
+		.map_err(|source| tg::error!(!source, "failed to finish the archive"))?;
+	file.flush()
+		.map_err(|source| tg::error!(!source, "failed to flush the archive"))?;
+	Ok(())
+}
+
+/// Write a zip archive, using `open` to read the contents of each file.
+fn write_zip<C, R>(
+	file: impl std::io::Write + std::io::Seek,
+	entries: Vec<(String, Entry<C>)>,
+	open: impl Fn(&C) -> tg::Result<R>,
+) -> tg::Result<()>
+where
+	R: std::io::Read,
+{
+	let mut writer = zip::ZipWriter::new(file);
+	let options = zip::write::SimpleFileOptions::default()
+		.last_modified_time(zip::DateTime::default())
+		.large_file(false);
+	for (path, entry) in entries {
+		match entry {
+			Entry::Directory => {
+				let options = options.unix_permissions(0o755);
+				writer
+					.add_directory(path.as_str(), options)
+					.map_err(|source| tg::error!(!source, %path, "failed to add the directory"))?;
+			},
+			Entry::File {
+				contents,
+				executable,
+				size,
+			} => {
+				let options = options
+					.compression_method(zip::CompressionMethod::Deflated)
+					.large_file(size >= 0xFFFF_FFFF)
+					.unix_permissions(if executable { 0o755 } else { 0o644 });
+				writer
+					.start_file(path.as_str(), options)
+					.map_err(|source| tg::error!(!source, %path, "failed to add the file"))?;
+				let mut reader = open(&contents)?;
+				std::io::copy(&mut reader, &mut writer)
+					.map_err(|source| tg::error!(!source, %path, "failed to write the file"))?;
+			},
+			Entry::Symlink { target } => {
+				let options = options.unix_permissions(0o777);
+				writer
+					.add_symlink(path.as_str(), target.as_str(), options)
+					.map_err(|source| tg::error!(!source, %path, "failed to add the symlink"))?;
+			},
+		}
+	}
+	let mut file = writer
+		.finish()
+		.map_err(|source| tg::error!(!source, "failed to finish the archive"))?;
+	file.flush()
+		.map_err(|source| tg::error!(!source, "failed to flush the archive"))?;
+	Ok(())
+}
+
+/// Create a synchronous reader for a blob from within a blocking task.
+fn blob_reader(server: &crate::Server, blob: &tg::Blob) -> tg::Result<impl std::io::Read> {
+	let reader = tokio::runtime::Handle::current().block_on(blob.reader(server))?;
+	Ok(SyncIoBridge::new(reader))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::os::unix::fs::PermissionsExt as _;
+
+	fn entries() -> Vec<(String, Entry<&'static [u8]>)> {
+		vec![
+			("directory".to_owned(), Entry::Directory),
+			(
+				"directory/hello.txt".to_owned(),
+				Entry::File {
+					contents: b"Hello, world!",
+					executable: false,
+					size: 13,
+				},
+			),
+			(
+				"directory/script.sh".to_owned(),
+				Entry::File {
+					contents: b"#!/bin/sh\n",
+					executable: true,
+					size: 10,
+				},
+			),
+			(
+				"link".to_owned(),
+				Entry::Symlink {
+					target: "directory/hello.txt".to_owned(),
+				},
+			),
+		]
+	}
+
+	fn assert_extracted(path: &std::path::Path) {
+		let hello = std::fs::read(path.join("directory/hello.txt")).unwrap();
+		assert_eq!(hello, b"Hello, world!");
+		let metadata = std::fs::metadata(path.join("directory/hello.txt")).unwrap();
+		assert_eq!(metadata.permissions().mode() & 0o111, 0);
+		let script = std::fs::read(path.join("directory/script.sh")).unwrap();
+		assert_eq!(script, b"#!/bin/sh\n");
+		let metadata = std::fs::metadata(path.join("directory/script.sh")).unwrap();
+		assert_ne!(metadata.permissions().mode() & 0o111, 0);
+		let target = std::fs::read_link(path.join("link")).unwrap();
+		assert_eq!(target, std::path::Path::new("directory/hello.txt"));
+	}
+
+	#[test]
+	fn tar_round_trip() {
+		let mut archive = std::io::Cursor::new(Vec::new());
+		write_tar(&mut archive, entries(), |contents| Ok(*contents)).unwrap();
+		archive.set_position(0);
+		let tmp = tempfile::tempdir().unwrap();
+		let path = tmp.path().join("archive");
+		super::super::extract::unpack_tar(archive, &path).unwrap();
+		assert_extracted(&path);
+	}
+
+	#[test]
+	fn zip_round_trip() {
+		let mut archive = std::io::Cursor::new(Vec::new());
+		write_zip(&mut archive, entries(), |contents| Ok(*contents)).unwrap();
+		archive.set_position(0);
+		let tmp = tempfile::tempdir().unwrap();
+		let path = tmp.path().join("archive");
+		super::super::extract::unpack_zip(archive, &path).unwrap();
+		assert_extracted(&path);
 	}
 }
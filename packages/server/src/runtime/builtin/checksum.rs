@@ -48,16 +48,13 @@ impl Runtime {
 
 	async fn checksum_artifact(
 		&self,
-		_artifact: &tg::Artifact,
+		artifact: &tg::Artifact,
 		algorithm: tg::checksum::Algorithm,
 	) -> tg::Result<tg::Checksum> {
-		match algorithm {
-			tg::checksum::Algorithm::Unsafe => Ok(tg::Checksum::Unsafe),
-			_ => Err(tg::error!("unimplemented")),
-		}
+		self.server.checksum_artifact(artifact, algorithm).await
 	}
 
-	async fn checksum_blob(
+	pub(super) async fn checksum_blob(
 		&self,
 		blob: &tg::Blob,
 		algorithm: tg::checksum::Algorithm,
@@ -37,7 +37,19 @@ impl Runtime {
 			.map_err(|source| tg::error!(!source, "invalid format"))?;
 
 		// Compress the blob.
-		let reader = blob.reader(server).await?;
+		let reader = self.compressed_reader(&blob, format).await?;
+		let blob = tg::Blob::with_reader(server, reader).await?;
+
+		Ok(blob.into())
+	}
+
+	/// Create a reader for a blob that compresses it with the given format.
+	pub(super) async fn compressed_reader(
+		&self,
+		blob: &tg::Blob,
+		format: tg::blob::compress::Format,
+	) -> tg::Result<Pin<Box<dyn AsyncRead + Send + 'static>>> {
+		let reader = blob.reader(&self.server).await?;
 		let reader: Pin<Box<dyn AsyncRead + Send + 'static>> = match format {
 			tg::blob::compress::Format::Bz2 => {
 				Box::pin(async_compression::tokio::bufread::BzEncoder::new(reader))
@@ -52,8 +64,6 @@ impl Runtime {
 				Box::pin(async_compression::tokio::bufread::ZstdEncoder::new(reader))
 			},
 		};
-		let blob = tg::Blob::with_reader(server, reader).await?;
-
-		Ok(blob.into())
+		Ok(reader)
 	}
 }
@@ -0,0 +1,403 @@
+use super::Runtime;
+use std::collections::BTreeMap;
+use tangram_client as tg;
+
+/// The media type of an OCI image index.
+const INDEX_MEDIA_TYPE: &str = "application/vnd.oci.image.index.v1+json";
+
+/// The media type of an OCI image manifest.
+const MANIFEST_MEDIA_TYPE: &str = "application/vnd.oci.image.manifest.v1+json";
+
+/// The media type of an OCI image config.
+const CONFIG_MEDIA_TYPE: &str = "application/vnd.oci.image.config.v1+json";
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Index {
+	schema_version: u32,
+	media_type: &'static str,
+	manifests: Vec<Descriptor>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+	schema_version: u32,
+	media_type: &'static str,
+	config: Descriptor,
+	layers: Vec<Descriptor>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+	media_type: &'static str,
+	digest: String,
+	size: u64,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	platform: Option<Platform>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct Platform {
+	architecture: String,
+	os: String,
+}
+
+#[allow(clippy::struct_field_names)]
+#[derive(serde::Serialize)]
+struct Config {
+	architecture: String,
+	os: String,
+	config: ContainerConfig,
+	rootfs: RootFs,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerConfig {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	user: Option<String>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	env: Vec<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	entrypoint: Option<Vec<String>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	cmd: Option<Vec<String>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	working_dir: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct RootFs {
+	#[serde(rename = "type")]
+	type_: &'static str,
+	diff_ids: Vec<String>,
+}
+
+impl Runtime {
+	pub async fn export_image(
+		&self,
+		build: &tg::Build,
+		_remote: Option<String>,
+	) -> tg::Result<tg::Value> {
+		let server = &self.server;
+
+		// Get the target.
+		let target = build.target(server).await?;
+
+		// Get the args.
+		let args = target.args(server).await?;
+
+		// Get the directory.
+		let directory = args
+			.get(1)
+			.ok_or_else(|| tg::error!("invalid number of arguments"))?
+			.clone()
+			.try_into()
+			.ok()
+			.and_then(|artifact: tg::Artifact| artifact.try_unwrap_directory().ok())
+			.ok_or_else(|| tg::error!("expected a directory"))?;
+
+		// Get the config.
+		let arg = args
+			.get(2)
+			.ok_or_else(|| tg::error!("invalid number of arguments"))?
+			.try_unwrap_map_ref()
+			.ok()
+			.ok_or_else(|| tg::error!("expected a map"))?;
+		let architecture = try_get_string(arg, "architecture")?
+			.ok_or_else(|| tg::error!("expected the architecture to be set"))?;
+		let compression = match try_get_string(arg, "compression")? {
+			None => tg::blob::compress::Format::Gz,
+			Some(format) => format.parse()?,
+		};
+		let layer_media_type = match compression {
+			tg::blob::compress::Format::Gz => "application/vnd.oci.image.layer.v1.tar+gzip",
+			tg::blob::compress::Format::Zstd => "application/vnd.oci.image.layer.v1.tar+zstd",
+			format => {
+				return Err(tg::error!(%format, "unsupported layer compression format"));
+			},
+		};
+		let env = match arg.get("env") {
+			None | Some(tg::Value::Null) => Vec::new(),
+			Some(value) => value
+				.try_unwrap_map_ref()
+				.ok()
+				.ok_or_else(|| tg::error!("expected the env to be a map"))?
+				.iter()
+				.filter(|(_, value)| !matches!(value, tg::Value::Null))
+				.map(|(key, value)| {
+					let value = value
+						.try_unwrap_string_ref()
+						.ok()
+						.ok_or_else(|| tg::error!(%key, "expected the env value to be a string"))?;
+					Ok(format!("{key}={value}"))
+				})
+				.collect::<tg::Result<_>>()?,
+		};
+		let container_config = ContainerConfig {
+			user: try_get_string(arg, "user")?,
+			env,
+			entrypoint: try_get_strings(arg, "entrypoint")?,
+			cmd: try_get_strings(arg, "cmd")?,
+			working_dir: try_get_string(arg, "workingDir")?,
+		};
+
+		// Create the layer. The archive's entries are sorted and have fixed metadata, so the layer's digest only depends on the directory.
+		let tar = self
+			.archive_directory(&directory, tg::artifact::archive::Format::Tar)
+			.await?;
+		let diff_id = self
+			.checksum_blob(&tar, tg::checksum::Algorithm::Sha256)
+			.await?
+			.to_string();
+		let reader = self.compressed_reader(&tar, compression).await?;
+		let layer = tg::Blob::with_reader(server, reader).await?;
+		let layer_descriptor = Descriptor {
+			media_type: layer_media_type,
+			digest: self
+				.checksum_blob(&layer, tg::checksum::Algorithm::Sha256)
+				.await?
+				.to_string(),
+			size: layer.size(server).await?,
+			platform: None,
+		};
+
+		// Create the config.
+		let platform = Platform {
+			architecture,
+			os: "linux".to_owned(),
+		};
+		let config = Config {
+			architecture: platform.architecture.clone(),
+			os: platform.os.clone(),
+			config: container_config,
+			rootfs: RootFs {
+				type_: "layers",
+				diff_ids: vec![diff_id],
+			},
+		};
+		let config = serialize(&config)?;
+		let config_descriptor = Descriptor {
+			media_type: CONFIG_MEDIA_TYPE,
+			digest: digest(&config),
+			size: config.len().try_into().unwrap(),
+			platform: None,
+		};
+
+		// Create the manifest.
+		let mut blobs = BTreeMap::new();
+		blobs.insert(layer_descriptor.digest.clone(), layer);
+		blobs.insert(config_descriptor.digest.clone(), config.into());
+		let manifest = Manifest {
+			schema_version: 2,
+			media_type: MANIFEST_MEDIA_TYPE,
+			config: config_descriptor,
+			layers: vec![layer_descriptor],
+		};
+		let manifest = serialize(&manifest)?;
+		let manifest_descriptor = Descriptor {
+			media_type: MANIFEST_MEDIA_TYPE,
+			digest: digest(&manifest),
+			size: manifest.len().try_into().unwrap(),
+			platform: Some(platform),
+		};
+		blobs.insert(manifest_descriptor.digest.clone(), manifest.into());
+
+		// Create the index.
+		let index = Index {
+			schema_version: 2,
+			media_type: INDEX_MEDIA_TYPE,
+			manifests: vec![manifest_descriptor],
+		};
+		let index = serialize(&index)?;
+
+		// Create the image layout.
+		let blobs = blobs
+			.into_iter()
+			.map(|(digest, blob)| {
+				let (_, encoded) = digest.split_once(':').unwrap();
+				let file = tg::File::with_contents(blob);
+				(encoded.to_owned(), file.into())
+			})
+			.collect();
+		let blobs = tg::Directory::with_entries(
+			[(
+				"sha256".to_owned(),
+				tg::Directory::with_entries(blobs).into(),
+			)]
+			.into(),
+		);
+		let layout = tg::Directory::with_entries(
+			[
+				("blobs".to_owned(), blobs.into()),
+				(
+					"index.json".to_owned(),
+					tg::File::with_contents(index).into(),
+				),
+				(
+					"oci-layout".to_owned(),
+					tg::File::with_contents(r#"{"imageLayoutVersion":"1.0.0"}"#).into(),
+				),
+			]
+			.into(),
+		);
+
+		// Store the image layout so that its files can be read, then archive it.
+		layout.store(server).await?;
+		let blob = self
+			.archive_directory(&layout, tg::artifact::archive::Format::Tar)
+			.await?;
+
+		Ok(blob.into())
+	}
+}
+
+fn try_get_string(map: &tg::value::Map, key: &str) -> tg::Result<Option<String>> {
+	match map.get(key) {
+		None | Some(tg::Value::Null) => Ok(None),
+		Some(value) => {
+			let value = value
+				.try_unwrap_string_ref()
+				.ok()
+				.ok_or_else(|| tg::error!(%key, "expected a string"))?;
+			Ok(Some(value.clone()))
+		},
+	}
+}
+
+fn try_get_strings(map: &tg::value::Map, key: &str) -> tg::Result<Option<Vec<String>>> {
+	match map.get(key) {
+		None | Some(tg::Value::Null) => Ok(None),
+		Some(value) => {
+			let values = value
+				.try_unwrap_array_ref()
+				.ok()
+				.ok_or_else(|| tg::error!(%key, "expected an array"))?
+				.iter()
+				.map(|value| {
+					let value = value
+						.try_unwrap_string_ref()
+						.ok()
+						.ok_or_else(|| tg::error!(%key, "expected an array of strings"))?;
+					Ok(value.clone())
+				})
+				.collect::<tg::Result<_>>()?;
+			Ok(Some(values))
+		},
+	}
+}
+
+fn serialize(value: &impl serde::Serialize) -> tg::Result<String> {
+	serde_json::to_string(value).map_err(|source| tg::error!(!source, "failed to serialize"))
+}
+
+/// Compute the OCI digest of some bytes.
+fn digest(bytes: &str) -> String {
+	let mut writer = tg::checksum::Writer::new(tg::checksum::Algorithm::Sha256);
+	writer.update(bytes);
+	writer.finalize().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::TestServer;
+	use std::{io::Read as _, path::Path};
+
+	/// Read a file from an image layout's tarball.
+	fn read(image: &[u8], path: &str) -> Vec<u8> {
+		let mut archive = tar::Archive::new(image);
+		for entry in archive.entries().unwrap() {
+			let mut entry = entry.unwrap();
+			if entry.path().unwrap() == Path::new(path) {
+				let mut bytes = Vec::new();
+				entry.read_to_end(&mut bytes).unwrap();
+				return bytes;
+			}
+		}
+		panic!("expected the image to contain {path}");
+	}
+
+	/// Read a JSON blob from an image layout's tarball.
+	fn read_blob(image: &[u8], digest: &str) -> Vec<u8> {
+		let (_, encoded) = digest.split_once(':').unwrap();
+		read(image, &format!("blobs/sha256/{encoded}"))
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn test_export_image() {
+		let server = TestServer::new().await;
+		let runtime = Runtime::new(&server);
+
+		// Create a directory.
+		let script = tg::File::builder(tg::Blob::from("#!/bin/sh\n"))
+			.executable(true)
+			.build();
+		let directory = tg::Directory::with_entries(
+			[
+				(
+					"hello.txt".to_owned(),
+					tg::File::with_contents("Hello, world!").into(),
+				),
+				(
+					"bin".to_owned(),
+					tg::Directory::with_entries([("script.sh".to_owned(), script.into())].into())
+						.into(),
+				),
+			]
+			.into(),
+		);
+		directory.store(&*server).await.unwrap();
+		let config = [(
+			"architecture".to_owned(),
+			tg::Value::from("amd64".to_owned()),
+		)]
+		.into_iter()
+		.collect::<tg::value::Map>();
+
+		// Export the directory twice.
+		let mut images = Vec::new();
+		for _ in 0..2 {
+			let args = vec![
+				"export_image".to_owned().into(),
+				directory.clone().into(),
+				config.clone().into(),
+			];
+			let build = server.put_started_build("builtin", args).await;
+			let blob: tg::Blob = runtime
+				.export_image(&build, None)
+				.await
+				.unwrap()
+				.try_into()
+				.unwrap();
+			let bytes = blob.bytes(&*server).await.unwrap();
+			images.push((blob, bytes));
+		}
+
+		// The exports must have identical indexes, manifests, and layers.
+		let [(blob, first), (_, second)] = images.try_into().ok().unwrap();
+		let index = read(&first, "index.json");
+		assert_eq!(index, read(&second, "index.json"));
+		let index = serde_json::from_slice::<serde_json::Value>(&index).unwrap();
+		let digest = index["manifests"][0]["digest"].as_str().unwrap();
+		let manifest = read_blob(&first, digest);
+		assert_eq!(manifest, read_blob(&second, digest));
+		let manifest = serde_json::from_slice::<serde_json::Value>(&manifest).unwrap();
+		let digest = manifest["layers"][0]["digest"].as_str().unwrap();
+		assert_eq!(read_blob(&first, digest), read_blob(&second, digest));
+
+		// Importing the image must produce the directory.
+		let args = vec!["import_image".to_owned().into(), blob.into()];
+		let build = server.put_started_build("builtin", args).await;
+		let imported: tg::Artifact = runtime
+			.import_image(&build, None)
+			.await
+			.unwrap()
+			.try_into()
+			.unwrap();
+		let expected: tg::artifact::Id = directory.id(&*server).await.unwrap().into();
+		assert_eq!(imported.id(&*server).await.unwrap(), expected);
+	}
+}
@@ -1,6 +1,8 @@
 use super::Runtime;
 use crate::tmp::Tmp;
+use std::{path::Path, pin::Pin};
 use tangram_client as tg;
+use tokio::io::{AsyncRead, AsyncReadExt as _};
 use tokio_util::io::SyncIoBridge;
 
 impl Runtime {
@@ -27,55 +29,68 @@ impl Runtime {
 			.ok_or_else(|| tg::error!("expected a blob"))?;
 
 		// Get the format.
-		let format = if let Some(value) = args.get(2) {
-			let format = value
-				.try_unwrap_string_ref()
-				.ok()
-				.ok_or_else(|| tg::error!("expected a string"))?
-				.parse::<tg::artifact::archive::Format>()
-				.map_err(|source| tg::error!(!source, "invalid format"))?;
-			Some(format)
-		} else {
-			None
+		let format = match args.get(2) {
+			None | Some(tg::Value::Null) => None,
+			Some(value) => {
+				let format = value
+					.try_unwrap_string_ref()
+					.ok()
+					.ok_or_else(|| tg::error!("expected a string"))?
+					.parse::<tg::artifact::archive::Format>()
+					.map_err(|source| tg::error!(!source, "invalid format"))?;
+				Some(format)
+			},
 		};
 
-		// Create the reader.
+		// Detect the compression format.
 		let reader = blob.reader(server).await?;
+		let compression = detect_compression_format(&read_prefix(reader, 6).await?);
+
+		// Detect the archive format if it was not provided.
+		let format = if let Some(format) = format {
+			format
+		} else {
+			let reader = self.decompressed_reader(&blob, compression).await?;
+			detect_archive_format(&read_prefix(reader, 512).await?)
+				.ok_or_else(|| tg::error!("failed to detect the archive format"))?
+		};
+
+		// Zip archives must be seekable, so decompress a compressed zip archive to a new blob first.
+		let blob = match (format, compression) {
+			(tg::artifact::archive::Format::Zip, Some(_)) => {
+				let reader = self.decompressed_reader(&blob, compression).await?;
+				tg::Blob::with_reader(server, reader).await?
+			},
+			_ => blob,
+		};
 
 		// Create a temporary path.
 		let tmp = Tmp::new(server);
 		let path = tmp.as_ref().join("archive");
 
 		// Extract in a blocking task.
-		tokio::task::spawn_blocking({
-			let reader = SyncIoBridge::new(reader);
-			let path = path.clone();
-			move || {
-				let format = format
-					.ok_or_else(|| tg::error!("archive format detection is unimplemented"))?;
-				match format {
-					tg::artifact::archive::Format::Tar => {
-						let mut archive = tar::Archive::new(reader);
-						archive.set_preserve_permissions(false);
-						archive.set_unpack_xattrs(false);
-						archive.unpack(path).map_err(|source| {
-							tg::error!(!source, "failed to extract the archive")
-						})?;
-					},
-					tg::artifact::archive::Format::Zip => {
-						let mut archive = zip::ZipArchive::new(reader).map_err(|source| {
-							tg::error!(!source, "failed to extract the archive")
-						})?;
-						archive.extract(&path).map_err(|source| {
-							tg::error!(!source, "failed to extract the archive")
-						})?;
-					},
-				}
-				Ok::<_, tg::Error>(())
-			}
-		})
-		.await
-		.unwrap()?;
+		match format {
+			tg::artifact::archive::Format::Tar => {
+				let reader = self.decompressed_reader(&blob, compression).await?;
+				tokio::task::spawn_blocking({
+					let reader = SyncIoBridge::new(reader);
+					let path = path.clone();
+					move || unpack_tar(reader, &path)
+				})
+				.await
+				.unwrap()?;
+			},
+			tg::artifact::archive::Format::Zip => {
+				let reader = blob.reader(server).await?;
+				tokio::task::spawn_blocking({
+					let reader = SyncIoBridge::new(reader);
+					let path = path.clone();
+					move || unpack_zip(reader, &path)
+				})
+				.await
+				.unwrap()?;
+			},
+		}
 
 		// Check in the extracted artifact.
 		let arg = tg::artifact::checkin::Arg {
@@ -91,4 +106,168 @@ impl Runtime {
 
 		Ok(artifact.into())
 	}
+
+	/// Create a reader for a blob that decompresses it with the given format, if any.
+	pub(super) async fn decompressed_reader(
+		&self,
+		blob: &tg::Blob,
+		compression: Option<tg::blob::compress::Format>,
+	) -> tg::Result<Pin<Box<dyn AsyncRead + Send + 'static>>> {
+		let reader = blob.reader(&self.server).await?;
+		let reader: Pin<Box<dyn AsyncRead + Send + 'static>> = match compression {
+			None => Box::pin(reader),
+			Some(tg::blob::compress::Format::Bz2) => {
+				Box::pin(async_compression::tokio::bufread::BzDecoder::new(reader))
+			},
+			Some(tg::blob::compress::Format::Gz) => {
+				Box::pin(async_compression::tokio::bufread::GzipDecoder::new(reader))
+			},
+			Some(tg::blob::compress::Format::Xz) => {
+				Box::pin(async_compression::tokio::bufread::XzDecoder::new(reader))
+			},
+			Some(tg::blob::compress::Format::Zstd) => {
+				Box::pin(async_compression::tokio::bufread::ZstdDecoder::new(reader))
+			},
+		};
+		Ok(reader)
+	}
+}
+
+/// Unpack a tar archive to a path.
+pub(super) fn unpack_tar(reader: impl std::io::Read, path: &Path) -> tg::Result<()> {
+	let mut archive = tar::Archive::new(reader);
+	archive.set_preserve_permissions(false);
+	archive.set_unpack_xattrs(false);
+	archive
+		.unpack(path)
+		.map_err(|source| tg::error!(!source, "failed to extract the archive"))?;
+	Ok(())
+}
+
+/// Unpack a zip archive to a path.
+pub(super) fn unpack_zip(
+	reader: impl std::io::Read + std::io::Seek,
+	path: &Path,
+) -> tg::Result<()> {
+	let mut archive = zip::ZipArchive::new(reader)
+		.map_err(|source| tg::error!(!source, "failed to extract the archive"))?;
+	archive
+		.extract(path)
+		.map_err(|source| tg::error!(!source, "failed to extract the archive"))?;
+	Ok(())
+}
+
+/// Read up to `length` bytes from the start of a reader.
+pub(super) async fn read_prefix(
+	reader: impl AsyncRead + Unpin,
+	length: u64,
+) -> tg::Result<Vec<u8>> {
+	let mut bytes = Vec::new();
+	reader
+		.take(length)
+		.read_to_end(&mut bytes)
+		.await
+		.map_err(|source| tg::error!(!source, "failed to read the blob"))?;
+	Ok(bytes)
+}
+
+/// Detect a compression format from its magic bytes.
+pub(super) fn detect_compression_format(bytes: &[u8]) -> Option<tg::blob::compress::Format> {
+	if bytes.starts_with(&[0x1f, 0x8b]) {
+		Some(tg::blob::compress::Format::Gz)
+	} else if bytes.starts_with(b"BZh") {
+		Some(tg::blob::compress::Format::Bz2)
+	} else if bytes.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+		Some(tg::blob::compress::Format::Xz)
+	} else if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+		Some(tg::blob::compress::Format::Zstd)
+	} else {
+		None
+	}
+}
+
+/// Detect an archive format from its magic bytes.
+fn detect_archive_format(bytes: &[u8]) -> Option<tg::artifact::archive::Format> {
+	if bytes.starts_with(b"PK\x03\x04") || bytes.starts_with(b"PK\x05\x06") {
+		Some(tg::artifact::archive::Format::Zip)
+	} else if bytes.get(257..262) == Some(b"ustar") {
+		Some(tg::artifact::archive::Format::Tar)
+	} else {
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn detect(bytes: &[u8]) -> Option<String> {
+		detect_compression_format(bytes).map(|format| format.to_string())
+	}
+
+	#[test]
+	fn compression_format() {
+		assert_eq!(detect(&[0x1f, 0x8b, 0x08, 0x00]).as_deref(), Some("gz"));
+		assert_eq!(detect(b"BZh91AY").as_deref(), Some("bz2"));
+		assert_eq!(
+			detect(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]).as_deref(),
+			Some("xz")
+		);
+		assert_eq!(
+			detect(&[0x28, 0xb5, 0x2f, 0xfd, 0x00]).as_deref(),
+			Some("zst")
+		);
+		assert_eq!(detect(b"PK\x03\x04"), None);
+		assert_eq!(detect(&[]), None);
+	}
+
+	#[test]
+	fn compression_format_truncated_magic() {
+		assert_eq!(detect(&[0x1f]), None);
+		assert_eq!(detect(&[0xfd, b'7', b'z', b'X', b'Z']), None);
+	}
+
+	fn tar(mut header: tar::Header) -> Vec<u8> {
+		let mut builder = tar::Builder::new(Vec::new());
+		header.set_entry_type(tar::EntryType::Regular);
+		header.set_mode(0o644);
+		header.set_size(5);
+		builder
+			.append_data(&mut header, "hello.txt", &b"hello"[..])
+			.unwrap();
+		builder.into_inner().unwrap()
+	}
+
+	fn zip(empty: bool) -> Vec<u8> {
+		let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+		if !empty {
+			let options = zip::write::SimpleFileOptions::default();
+			writer.start_file("hello.txt", options).unwrap();
+			std::io::Write::write_all(&mut writer, b"hello").unwrap();
+		}
+		writer.finish().unwrap().into_inner()
+	}
+
+	#[test]
+	fn archive_format() {
+		let detect = |bytes: &[u8]| detect_archive_format(bytes).map(|format| format.to_string());
+
+		// Detect tar archives with both ustar and gnu headers.
+		let bytes = tar(tar::Header::new_ustar());
+		assert_eq!(detect(&bytes).as_deref(), Some("tar"));
+		let bytes = tar(tar::Header::new_gnu());
+		assert_eq!(detect(&bytes).as_deref(), Some("tar"));
+
+		// Detect zip archives, including an empty one that consists of only the end of central directory record.
+		assert_eq!(detect(&zip(false)).as_deref(), Some("zip"));
+		assert_eq!(detect(&zip(true)).as_deref(), Some("zip"));
+
+		// Do not detect unknown input.
+		assert_eq!(detect(&[]), None);
+		assert_eq!(detect(b"hello"), None);
+		assert_eq!(detect(&[0; 512]), None);
+		assert_eq!(detect(&[0x1f, 0x8b, 0x08, 0x00]), None);
+		let bytes = tar(tar::Header::new_ustar());
+		assert_eq!(detect(&bytes[..261]), None);
+	}
 }
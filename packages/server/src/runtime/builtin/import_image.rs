@@ -0,0 +1,683 @@
+use super::{
+	extract::{detect_compression_format, read_prefix},
+	Runtime,
+};
+use crate::tmp::Tmp;
+use std::{
+	collections::HashSet,
+	path::{Component, Path, PathBuf},
+	pin::Pin,
+};
+use tangram_client as tg;
+use tokio::io::{AsyncRead, AsyncSeekExt as _, BufReader};
+use tokio_util::io::SyncIoBridge;
+
+/// The prefix of a whiteout file's name.
+const WHITEOUT_PREFIX: &str = ".wh.";
+
+/// The name of an opaque whiteout file.
+const OPAQUE_WHITEOUT: &str = ".wh..wh..opq";
+
+#[derive(serde::Deserialize)]
+struct OciIndex {
+	manifests: Vec<OciDescriptor>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OciDescriptor {
+	#[serde(default)]
+	media_type: Option<String>,
+	digest: String,
+	#[serde(default)]
+	platform: Option<OciPlatform>,
+}
+
+#[derive(serde::Deserialize)]
+struct OciPlatform {
+	architecture: String,
+	os: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OciManifest {
+	#[serde(default)]
+	media_type: Option<String>,
+	#[serde(default)]
+	manifests: Option<Vec<OciDescriptor>>,
+	#[serde(default)]
+	layers: Vec<OciDescriptor>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DockerManifest {
+	layers: Vec<String>,
+}
+
+impl Runtime {
+	pub async fn import_image(
+		&self,
+		build: &tg::Build,
+		_remote: Option<String>,
+	) -> tg::Result<tg::Value> {
+		let server = &self.server;
+
+		// Get the target.
+		let target = build.target(server).await?;
+
+		// Get the args.
+		let args = target.args(server).await?;
+
+		// Get the blob.
+		let blob: tg::Blob = args
+			.get(1)
+			.ok_or_else(|| tg::error!("invalid number of arguments"))?
+			.clone()
+			.try_into()
+			.ok()
+			.ok_or_else(|| tg::error!("expected a blob"))?;
+
+		// Extract the image's tarball to a temporary directory.
+		let image_tmp = Tmp::new(server);
+		let image_path = image_tmp.as_ref().to_owned();
+		let reader = blob.reader(server).await?;
+		let compression = detect_compression_format(&read_prefix(reader, 6).await?);
+		let reader = self.decompressed_reader(&blob, compression).await?;
+		tokio::task::spawn_blocking({
+			let reader = SyncIoBridge::new(reader);
+			let path = image_path.clone();
+			move || {
+				let mut archive = tar::Archive::new(reader);
+				archive.set_preserve_permissions(false);
+				archive.set_unpack_xattrs(false);
+				archive
+					.unpack(path)
+					.map_err(|source| tg::error!(!source, "failed to extract the image"))?;
+				Ok::<_, tg::Error>(())
+			}
+		})
+		.await
+		.unwrap()?;
+
+		// Get the paths of the image's layers.
+		let layers = image_layer_paths(&image_path).await?;
+
+		// Apply the layers in order.
+		let rootfs_tmp = Tmp::new(server);
+		let rootfs_path = rootfs_tmp.as_ref().to_owned();
+		tokio::fs::create_dir_all(&rootfs_path)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to create the rootfs directory"))?;
+		for layer in layers {
+			let reader = layer_reader(&layer).await?;
+			tokio::task::spawn_blocking({
+				let reader = SyncIoBridge::new(reader);
+				let rootfs_path = rootfs_path.clone();
+				move || apply_layer(reader, &rootfs_path)
+			})
+			.await
+			.unwrap()
+			.map_err(
+				|source| tg::error!(!source, %layer = layer.display(), "failed to apply the layer"),
+			)?;
+		}
+
+		// Check in the rootfs.
+		let arg = tg::artifact::checkin::Arg {
+			destructive: true,
+			deterministic: true,
+			ignore: false,
+			locked: true,
+			path: rootfs_path,
+		};
+		let directory = tg::Artifact::check_in(server, arg)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to check in the rootfs"))?
+			.try_unwrap_directory()
+			.ok()
+			.ok_or_else(|| tg::error!("expected the rootfs to be a directory"))?;
+
+		Ok(directory.into())
+	}
+}
+
+/// Get the paths of an extracted image's layers, from either an OCI image layout or a `docker save` tarball.
+async fn image_layer_paths(path: &Path) -> tg::Result<Vec<PathBuf>> {
+	// Prefer the OCI image layout's index.
+	let index_path = path.join("index.json");
+	if tokio::fs::try_exists(&index_path).await.unwrap_or(false) {
+		let index: OciIndex = read_json(&index_path).await?;
+		let mut descriptors = index.manifests;
+		loop {
+			let descriptor = select_manifest(descriptors)?;
+			let manifest: OciManifest = read_json(&blob_path(path, &descriptor.digest)?).await?;
+			let is_index = manifest
+				.media_type
+				.as_deref()
+				.or(descriptor.media_type.as_deref())
+				.is_some_and(|media_type| media_type.ends_with("index.v1+json"))
+				|| manifest.manifests.is_some();
+			if is_index {
+				descriptors = manifest.manifests.unwrap_or_default();
+				continue;
+			}
+			return manifest
+				.layers
+				.iter()
+				.map(|layer| blob_path(path, &layer.digest))
+				.collect();
+		}
+	}
+
+	// Otherwise, use the `docker save` manifest.
+	let manifest_path = path.join("manifest.json");
+	if tokio::fs::try_exists(&manifest_path).await.unwrap_or(false) {
+		let manifests: Vec<DockerManifest> = read_json(&manifest_path).await?;
+		let manifest = manifests
+			.into_iter()
+			.next()
+			.ok_or_else(|| tg::error!("the image's manifest is empty"))?;
+		return manifest
+			.layers
+			.iter()
+			.map(|layer| {
+				let layer = normalize(Path::new(layer))
+					.ok_or_else(|| tg::error!(%layer, "invalid layer path"))?;
+				Ok(path.join(layer))
+			})
+			.collect();
+	}
+
+	Err(tg::error!(
+		"expected an OCI image layout or a docker save tarball"
+	))
+}
+
+/// Select the manifest for this host's platform. If none of the manifests has a platform, then select the first manifest.
+fn select_manifest(descriptors: Vec<OciDescriptor>) -> tg::Result<OciDescriptor> {
+	let architecture = host_architecture();
+	let index = descriptors.iter().position(|descriptor| {
+		descriptor
+			.platform
+			.as_ref()
+			.is_some_and(|platform| platform.os == "linux" && platform.architecture == architecture)
+	});
+	let index = match index {
+		Some(index) => index,
+		None if descriptors
+			.iter()
+			.all(|descriptor| descriptor.platform.is_none()) =>
+		{
+			0
+		},
+		None => {
+			return Err(tg::error!(
+				%architecture,
+				"the image has no manifest for this host's platform"
+			));
+		},
+	};
+	descriptors
+		.into_iter()
+		.nth(index)
+		.ok_or_else(|| tg::error!("the image index has no manifests"))
+}
+
+/// Get this host's architecture as it is named in OCI image platforms.
+fn host_architecture() -> &'static str {
+	match std::env::consts::ARCH {
+		"aarch64" => "arm64",
+		"x86_64" => "amd64",
+		architecture => architecture,
+	}
+}
+
+/// Get the path of a blob in an OCI image layout.
+fn blob_path(path: &Path, digest: &str) -> tg::Result<PathBuf> {
+	let (algorithm, encoded) = digest
+		.split_once(':')
+		.ok_or_else(|| tg::error!(%digest, "invalid digest"))?;
+	let valid = |string: &str| {
+		!string.is_empty()
+			&& string
+				.chars()
+				.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '.' | '_' | '-'))
+	};
+	if !valid(algorithm) || !valid(encoded) {
+		return Err(tg::error!(%digest, "invalid digest"));
+	}
+	Ok(path.join("blobs").join(algorithm).join(encoded))
+}
+
+async fn read_json<T>(path: &Path) -> tg::Result<T>
+where
+	T: serde::de::DeserializeOwned,
+{
+	let bytes = tokio::fs::read(path)
+		.await
+		.map_err(|source| tg::error!(!source, %path = path.display(), "failed to read the file"))?;
+	serde_json::from_slice(&bytes).map_err(
+		|source| tg::error!(!source, %path = path.display(), "failed to deserialize the file"),
+	)
+}
+
+/// Create a reader for a layer that decompresses it if necessary.
+async fn layer_reader(path: &Path) -> tg::Result<Pin<Box<dyn AsyncRead + Send + 'static>>> {
+	let mut file = tokio::fs::File::open(path).await.map_err(
+		|source| tg::error!(!source, %path = path.display(), "failed to open the layer"),
+	)?;
+	let prefix = read_prefix(&mut file, 6).await?;
+	file.rewind()
+		.await
+		.map_err(|source| tg::error!(!source, "failed to seek the layer"))?;
+	let reader = BufReader::new(file);
+	let reader: Pin<Box<dyn AsyncRead + Send + 'static>> = match detect_compression_format(&prefix)
+	{
+		None => Box::pin(reader),
+		Some(tg::blob::compress::Format::Bz2) => {
+			Box::pin(async_compression::tokio::bufread::BzDecoder::new(reader))
+		},
+		Some(tg::blob::compress::Format::Gz) => {
+			Box::pin(async_compression::tokio::bufread::GzipDecoder::new(reader))
+		},
+		Some(tg::blob::compress::Format::Xz) => {
+			Box::pin(async_compression::tokio::bufread::XzDecoder::new(reader))
+		},
+		Some(tg::blob::compress::Format::Zstd) => {
+			Box::pin(async_compression::tokio::bufread::ZstdDecoder::new(reader))
+		},
+	};
+	Ok(reader)
+}
+
+/// Apply a layer to a rootfs, honoring whiteout files.
+fn apply_layer(reader: impl std::io::Read, rootfs: &Path) -> tg::Result<()> {
+	// Track the paths that this layer creates, so that opaque whiteouts only hide the lower layers' entries.
+	let mut created = HashSet::new();
+
+	let mut archive = tar::Archive::new(reader);
+	let entries = archive
+		.entries()
+		.map_err(|source| tg::error!(!source, "failed to read the layer"))?;
+	for entry in entries {
+		let mut entry = entry.map_err(|source| tg::error!(!source, "failed to read the layer"))?;
+		let entry_path = entry
+			.path()
+			.map_err(|source| tg::error!(!source, "failed to read the entry's path"))?;
+		let Some(path) = normalize(&entry_path) else {
+			continue;
+		};
+		let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+			continue;
+		};
+		let parent = path.parent().unwrap_or(Path::new("")).to_owned();
+
+		// Get the entry's path in the rootfs without following symlinks.
+		let target = resolve(rootfs, &path)?;
+
+		// Handle an opaque whiteout by removing the lower layers' entries in its directory.
+		if name == OPAQUE_WHITEOUT {
+			let directory = target.parent().unwrap();
+			let Ok(metadata) = std::fs::symlink_metadata(directory) else {
+				continue;
+			};
+			if !metadata.is_dir() {
+				continue;
+			}
+			let read_dir = std::fs::read_dir(directory).map_err(
+				|source| tg::error!(!source, %path = directory.display(), "failed to read the directory"),
+			)?;
+			for child in read_dir {
+				let child =
+					child.map_err(|source| tg::error!(!source, "failed to read the directory"))?;
+				let child = parent.join(child.file_name());
+				if !created.contains(&child) {
+					remove(&rootfs.join(&child))?;
+				}
+			}
+			continue;
+		}
+
+		// Handle a whiteout by removing the lower layers' entry.
+		if let Some(name) = name.strip_prefix(WHITEOUT_PREFIX) {
+			if matches!(name, "" | "." | "..") {
+				return Err(tg::error!(%path = path.display(), "invalid whiteout"));
+			}
+			remove(&target.with_file_name(name))?;
+			continue;
+		}
+
+		// Skip entries that cannot be represented as artifacts, such as devices and FIFOs.
+		let entry_type = entry.header().entry_type();
+		if !matches!(
+			entry_type,
+			tar::EntryType::Regular
+				| tar::EntryType::Continuous
+				| tar::EntryType::Directory
+				| tar::EntryType::Symlink
+				| tar::EntryType::Link
+		) {
+			continue;
+		}
+
+		// Replace an existing entry unless both are directories.
+		if let Ok(metadata) = std::fs::symlink_metadata(&target) {
+			if !(metadata.is_dir() && entry_type == tar::EntryType::Directory) {
+				remove(&target)?;
+			}
+		}
+
+		// Unpack the entry.
+		entry.set_preserve_permissions(false);
+		entry.set_unpack_xattrs(false);
+		entry.unpack_in(rootfs).map_err(
+			|source| tg::error!(!source, %path = path.display(), "failed to unpack the entry"),
+		)?;
+
+		// Make directories writable so that later layers can modify them, and preserve files' executable bits.
+		if entry_type == tar::EntryType::Directory {
+			set_mode(&target, 0o755)?;
+		} else if entry_type != tar::EntryType::Symlink {
+			let mode = entry.header().mode().unwrap_or(0o644);
+			let mode = if mode & 0o111 != 0 { 0o755 } else { 0o644 };
+			set_mode(&target, mode)?;
+		}
+
+		created.insert(path);
+	}
+
+	Ok(())
+}
+
+/// Normalize a path within a layer, returning `None` for the root or a path that escapes it.
+fn normalize(path: &Path) -> Option<PathBuf> {
+	let mut normalized = PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::Normal(component) => normalized.push(component),
+			Component::ParentDir => {
+				if !normalized.pop() {
+					return None;
+				}
+			},
+			Component::CurDir | Component::RootDir | Component::Prefix(_) => (),
+		}
+	}
+	if normalized.as_os_str().is_empty() {
+		None
+	} else {
+		Some(normalized)
+	}
+}
+
+/// Get the path of a normalized path within a rootfs. Every ancestor of the path that exists in the rootfs must not be a symlink, so that the path cannot leave the rootfs.
+fn resolve(rootfs: &Path, path: &Path) -> tg::Result<PathBuf> {
+	let mut resolved = rootfs.to_owned();
+	let mut components = path.components().peekable();
+	while let Some(component) = components.next() {
+		let Component::Normal(component) = component else {
+			return Err(tg::error!(%path = path.display(), "invalid path"));
+		};
+		resolved.push(component);
+		if components.peek().is_none() {
+			break;
+		}
+		match std::fs::symlink_metadata(&resolved) {
+			Ok(metadata) if metadata.is_symlink() => {
+				return Err(tg::error!(
+					%path = path.display(),
+					"the path's parent is a symlink"
+				));
+			},
+			Ok(_) => (),
+			Err(error) if error.kind() == std::io::ErrorKind::NotFound => break,
+			Err(source) => {
+				return Err(
+					tg::error!(!source, %path = resolved.display(), "failed to get the metadata"),
+				);
+			},
+		}
+	}
+	Ok(resolved)
+}
+
+/// Remove a file, directory, or symlink if it exists.
+fn remove(path: &Path) -> tg::Result<()> {
+	let Ok(metadata) = std::fs::symlink_metadata(path) else {
+		return Ok(());
+	};
+	let result = if metadata.is_dir() {
+		std::fs::remove_dir_all(path)
+	} else {
+		std::fs::remove_file(path)
+	};
+	result
+		.map_err(|source| tg::error!(!source, %path = path.display(), "failed to remove the path"))
+}
+
+fn set_mode(path: &Path, mode: u32) -> tg::Result<()> {
+	use std::os::unix::fs::PermissionsExt as _;
+	let permissions = std::fs::Permissions::from_mode(mode);
+	std::fs::set_permissions(path, permissions).map_err(
+		|source| tg::error!(!source, %path = path.display(), "failed to set the permissions"),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Create a layer from a list of paths and contents. Paths without contents are directories.
+	fn layer(entries: &[(&str, Option<&str>)]) -> Vec<u8> {
+		let mut builder = tar::Builder::new(Vec::new());
+		for (path, contents) in entries {
+			let mut header = tar::Header::new_gnu();
+			let contents = contents.unwrap_or_default();
+			if path.ends_with('/') {
+				header.set_entry_type(tar::EntryType::Directory);
+				header.set_mode(0o755);
+			} else {
+				header.set_entry_type(tar::EntryType::Regular);
+				header.set_mode(0o644);
+			}
+			header.set_size(contents.len().try_into().unwrap());
+			builder
+				.append_data(&mut header, path, contents.as_bytes())
+				.unwrap();
+		}
+		builder.into_inner().unwrap()
+	}
+
+	fn list(path: &Path) -> Vec<String> {
+		let mut names = std::fs::read_dir(path)
+			.unwrap()
+			.map(|entry| entry.unwrap().file_name().into_string().unwrap())
+			.collect::<Vec<_>>();
+		names.sort();
+		names
+	}
+
+	#[test]
+	fn test_normalize() {
+		let cases = [
+			("a/b", Some("a/b")),
+			("./a/b", Some("a/b")),
+			("/a/b", Some("a/b")),
+			("a/./b/", Some("a/b")),
+			("a/../b", Some("b")),
+			("a/b/..", Some("a")),
+			(".", None),
+			("/", None),
+			("a/..", None),
+			("..", None),
+			("../a", None),
+			("a/../../b", None),
+		];
+		for (path, expected) in cases {
+			assert_eq!(
+				normalize(Path::new(path)),
+				expected.map(PathBuf::from),
+				"{path}"
+			);
+		}
+	}
+
+	#[test]
+	fn test_whiteouts() {
+		let temp = tempfile::tempdir().unwrap();
+		let rootfs = temp.path();
+
+		// Apply a lower layer.
+		let lower = layer(&[
+			("a/", None),
+			("a/x", Some("x")),
+			("a/y", Some("y")),
+			("b/", None),
+			("b/z", Some("z")),
+			("c", Some("c")),
+		]);
+		apply_layer(lower.as_slice(), rootfs).unwrap();
+
+		// A whiteout removes the lower layers' entry, and an opaque whiteout removes all of the lower layers' entries in its directory but not the entries that its own layer creates.
+		let upper = layer(&[
+			("a/.wh.x", Some("")),
+			("b/w", Some("w")),
+			("b/.wh..wh..opq", Some("")),
+			(".wh.c", Some("")),
+		]);
+		apply_layer(upper.as_slice(), rootfs).unwrap();
+		assert_eq!(list(rootfs), ["a", "b"]);
+		assert_eq!(list(&rootfs.join("a")), ["y"]);
+		assert_eq!(list(&rootfs.join("b")), ["w"]);
+
+		// A whiteout of the parent directory is rejected.
+		let invalid = layer(&[("a/.wh..", Some(""))]);
+		assert!(apply_layer(invalid.as_slice(), rootfs).is_err());
+		assert_eq!(list(rootfs), ["a", "b"]);
+	}
+
+	#[test]
+	fn test_symlinked_parent() {
+		let temp = tempfile::tempdir().unwrap();
+		let rootfs = temp.path().join("rootfs");
+		let outside = temp.path().join("outside");
+		std::fs::create_dir_all(&rootfs).unwrap();
+		std::fs::create_dir_all(&outside).unwrap();
+		std::fs::write(outside.join("file"), "file").unwrap();
+		std::os::unix::fs::symlink(&outside, rootfs.join("link")).unwrap();
+
+		// A whiteout must not follow a symlink out of the rootfs.
+		let whiteout = layer(&[("link/.wh.file", Some(""))]);
+		assert!(apply_layer(whiteout.as_slice(), &rootfs).is_err());
+		assert!(outside.join("file").exists());
+
+		// An opaque whiteout must not follow a symlink out of the rootfs.
+		let opaque = layer(&[("link/.wh..wh..opq", Some(""))]);
+		assert!(apply_layer(opaque.as_slice(), &rootfs).is_err());
+		assert!(outside.join("file").exists());
+
+		// An entry must not replace a path behind a symlink.
+		let entry = layer(&[("link/file", Some("replaced"))]);
+		assert!(apply_layer(entry.as_slice(), &rootfs).is_err());
+		assert_eq!(
+			std::fs::read_to_string(outside.join("file")).unwrap(),
+			"file"
+		);
+	}
+
+	/// Get a digest for a name. The digests are not checked, so they do not need to be the blobs' hashes.
+	fn digest(name: &str) -> String {
+		format!("sha256:{}", data_encoding::HEXLOWER.encode(name.as_bytes()))
+	}
+
+	/// Write a JSON blob with a name to an OCI image layout and get its digest.
+	fn write_blob(path: &Path, name: &str, json: &serde_json::Value) -> String {
+		let digest = digest(name);
+		let blob = blob_path(path, &digest).unwrap();
+		std::fs::create_dir_all(blob.parent().unwrap()).unwrap();
+		std::fs::write(blob, json.to_string()).unwrap();
+		digest
+	}
+
+	#[tokio::test]
+	async fn test_oci_layer_paths() {
+		let temp = tempfile::tempdir().unwrap();
+		let path = temp.path();
+
+		// Create an index whose manifest is a nested index with a manifest for another platform and one for this host's platform.
+		let other = write_blob(
+			path,
+			"other",
+			&serde_json::json!({ "layers": [{ "digest": digest("other_layer") }] }),
+		);
+		let host = write_blob(
+			path,
+			"host",
+			&serde_json::json!({ "layers": [{ "digest": digest("a") }, { "digest": digest("b") }] }),
+		);
+		let other_architecture = if host_architecture() == "amd64" {
+			"arm64"
+		} else {
+			"amd64"
+		};
+		let nested = write_blob(
+			path,
+			"nested",
+			&serde_json::json!({
+				"mediaType": "application/vnd.oci.image.index.v1+json",
+				"manifests": [
+					{ "digest": other, "platform": { "architecture": other_architecture, "os": "linux" } },
+					{ "digest": host, "platform": { "architecture": host_architecture(), "os": "linux" } },
+				],
+			}),
+		);
+		let index = serde_json::json!({ "manifests": [{ "digest": nested }] });
+		std::fs::write(path.join("index.json"), index.to_string()).unwrap();
+		let paths = image_layer_paths(path).await.unwrap();
+		let expected = [digest("a"), digest("b")]
+			.iter()
+			.map(|digest| blob_path(path, digest).unwrap())
+			.collect::<Vec<_>>();
+		assert_eq!(paths, expected);
+
+		// An index with platforms but none for this host's platform is rejected.
+		let index = serde_json::json!({
+			"manifests": [
+				{ "digest": other, "platform": { "architecture": other_architecture, "os": "linux" } },
+			],
+		});
+		std::fs::write(path.join("index.json"), index.to_string()).unwrap();
+		assert!(image_layer_paths(path).await.is_err());
+
+		// An invalid digest is rejected.
+		let index = serde_json::json!({ "manifests": [{ "digest": "sha256:../../index.json" }] });
+		std::fs::write(path.join("index.json"), index.to_string()).unwrap();
+		assert!(image_layer_paths(path).await.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_docker_layer_paths() {
+		let temp = tempfile::tempdir().unwrap();
+		let path = temp.path();
+
+		// The layers of the first manifest are used, with their paths normalized.
+		let manifest = serde_json::json!([
+			{ "Layers": ["a/layer.tar", "./b/layer.tar"] },
+			{ "Layers": ["c/layer.tar"] },
+		]);
+		std::fs::write(path.join("manifest.json"), manifest.to_string()).unwrap();
+		let paths = image_layer_paths(path).await.unwrap();
+		assert_eq!(paths, [path.join("a/layer.tar"), path.join("b/layer.tar")]);
+
+		// A layer path outside of the image is rejected.
+		let manifest = serde_json::json!([{ "Layers": ["../layer.tar"] }]);
+		std::fs::write(path.join("manifest.json"), manifest.to_string()).unwrap();
+		assert!(image_layer_paths(path).await.is_err());
+
+		// A directory that is neither layout is rejected.
+		std::fs::remove_file(path.join("manifest.json")).unwrap();
+		assert!(image_layer_paths(path).await.is_err());
+	}
+}
@@ -26,6 +26,25 @@ pub struct Runtime {
 
 impl Runtime {
 	pub fn new(server: &Server) -> Self {
+		// Build limits and mounts are only supported on Linux.
+		let limits = server
+			.options
+			.build
+			.as_ref()
+			.map(|options| options.limits.clone())
+			.unwrap_or_default();
+		if limits.cpu.is_some() || limits.memory.is_some() || limits.pids.is_some() {
+			tracing::warn!("the build limits are not enforced on this platform");
+		}
+		if server
+			.options
+			.build
+			.as_ref()
+			.is_some_and(|options| !options.mounts.is_empty())
+		{
+			tracing::warn!("the build mounts are not mounted on this platform");
+		}
+
 		Self {
 			server: server.clone(),
 		}
@@ -115,7 +134,13 @@ impl Runtime {
 		// Start the proxy server.
 		let proxy = Proxy::new(server.clone(), build.id().clone(), remote.clone(), None);
 		let stop = Stop::new();
-		let proxy_task = tokio::spawn(Server::serve(proxy, proxy_server_url.clone(), stop));
+		let proxy_task = tokio::spawn(Server::serve(
+			proxy,
+			proxy_server_url.clone(),
+			None,
+			None,
+			stop,
+		));
 
 		// Render the executable.
 		let executable = target.executable(server).await?;
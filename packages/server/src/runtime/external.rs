@@ -0,0 +1,224 @@
+use super::proxy::Proxy;
+use crate::{tmp::Tmp, Server};
+use bytes::Bytes;
+use tangram_client as tg;
+use tangram_futures::task::Stop;
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+use tokio_util::task::AbortOnDropHandle;
+use url::Url;
+
+/// A runtime that runs an external executable for each build. The executable receives the build's ID as its last argument and a proxy server's URL in `TANGRAM_URL`. Its stdout and stderr are written to the build's log, and it returns its output by finishing its build through the proxy server, which it must do before it exits successfully.
+#[derive(Clone)]
+pub struct Runtime {
+	server: Server,
+	options: crate::options::Runtime,
+}
+
+impl Runtime {
+	pub fn new(server: &Server, options: crate::options::Runtime) -> Self {
+		Self {
+			server: server.clone(),
+			options,
+		}
+	}
+
+	pub async fn build(&self, build: &tg::Build, remote: Option<String>) -> tg::Result<tg::Value> {
+		let server = &self.server;
+
+		// Create a tempdir for the proxy server's socket.
+		let tmp = Tmp::new(server);
+		tokio::fs::create_dir_all(&tmp)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to create the temporary directory"))?;
+
+		// Create the proxy server URL.
+		let proxy_server_socket_path = tmp.as_ref().join("socket");
+		let proxy_server_socket_path = proxy_server_socket_path.display().to_string();
+		let proxy_server_socket_path = urlencoding::encode(&proxy_server_socket_path);
+		let proxy_server_url = format!("http+unix://{proxy_server_socket_path}");
+		let proxy_server_url = Url::parse(&proxy_server_url)
+			.map_err(|source| tg::error!(!source, "failed to parse the proxy server url"))?;
+
+		// Start the proxy server. The task is aborted if the build fails before it is stopped.
+		let proxy = Proxy::new(server.clone(), build.id().clone(), remote.clone(), None);
+		let stop = Stop::new();
+		let proxy_task = AbortOnDropHandle::new(tokio::spawn(Server::serve(
+			proxy.clone(),
+			proxy_server_url.clone(),
+			None,
+			None,
+			stop.clone(),
+		)));
+
+		// Create the command.
+		let executable = &self.options.executable;
+		let mut command = tokio::process::Command::new(executable);
+		command
+			.args(&self.options.args)
+			.arg(build.id().to_string())
+			.env("TANGRAM_BUILD", build.id().to_string())
+			.env("TANGRAM_URL", proxy_server_url.to_string())
+			.current_dir(&tmp)
+			.stdin(std::process::Stdio::null())
+			.stdout(std::process::Stdio::piped())
+			.stderr(std::process::Stdio::piped())
+			.kill_on_drop(true);
+
+		// Spawn the child.
+		let mut child = command.spawn().map_err(
+			|source| tg::error!(!source, %executable = executable.display(), "failed to spawn the runtime"),
+		)?;
+
+		// Spawn the log tasks.
+		let log_tasks = [
+			child
+				.stdout
+				.take()
+				.map(|reader| Box::pin(reader) as LogReader),
+			child
+				.stderr
+				.take()
+				.map(|reader| Box::pin(reader) as LogReader),
+		]
+		.into_iter()
+		.flatten()
+		.map(|mut reader| {
+			tokio::task::spawn({
+				let server = server.clone();
+				let build = build.clone();
+				let remote = remote.clone();
+				async move {
+					let mut buffer = vec![0; 4096];
+					loop {
+						let size = reader
+							.read(&mut buffer)
+							.await
+							.map_err(|source| tg::error!(!source, "failed to read from the log"))?;
+						if size == 0 {
+							return Ok::<_, tg::Error>(());
+						}
+						let bytes = Bytes::copy_from_slice(&buffer[0..size]);
+						if server.options.advanced.write_build_logs_to_stderr {
+							tokio::io::stderr()
+								.write_all(&bytes)
+								.await
+								.inspect_err(|error| {
+									tracing::error!(
+										?error,
+										"failed to write the build log to stderr"
+									);
+								})
+								.ok();
+						}
+						let arg = tg::build::log::post::Arg {
+							bytes,
+							remote: remote.clone(),
+						};
+						build.add_log(&server, arg).await?;
+					}
+				}
+			})
+		})
+		.collect::<Vec<_>>();
+
+		// Wait for the process to exit.
+		let exit_status = child
+			.wait()
+			.await
+			.map_err(|source| tg::error!(!source, "failed to wait for the process to exit"))?;
+
+		// Wait for the log tasks to complete.
+		for log_task in log_tasks {
+			log_task
+				.await
+				.map_err(|source| tg::error!(!source, "failed to join the log task"))?
+				.map_err(|source| tg::error!(!source, "the log task failed"))?;
+		}
+
+		// Stop the proxy server.
+		stop.stop();
+		proxy_task
+			.await
+			.map_err(|source| tg::error!(!source, "failed to join the proxy server task"))?
+			.map_err(|source| tg::error!(!source, "the proxy server failed"))?;
+
+		// Return an error if the process did not exit successfully.
+		if !exit_status.success() {
+			return Err(tg::error!("the process did not exit successfully"));
+		}
+
+		// Get the output from the outcome that the process finished the build with.
+		let value = match proxy.take_outcome() {
+			None => {
+				return Err(tg::error!(
+					"the process exited successfully without finishing the build"
+				));
+			},
+			Some(tg::build::outcome::Data::Canceled) => {
+				return Err(tg::error!("the build was canceled"));
+			},
+			Some(tg::build::outcome::Data::Failed(error)) => {
+				return Err(error);
+			},
+			Some(tg::build::outcome::Data::Succeeded(data)) => tg::Value::try_from(data)?,
+		};
+
+		Ok(value)
+	}
+}
+
+type LogReader = std::pin::Pin<Box<dyn tokio::io::AsyncRead + Send + 'static>>;
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::TestServer;
+	use indoc::formatdoc;
+	use std::os::unix::fs::PermissionsExt as _;
+
+	/// Create a runtime that runs a shell script.
+	fn script_runtime(server: &TestServer, name: &str, script: &str) -> Runtime {
+		let executable = server.temp_path().join(name);
+		std::fs::write(&executable, format!("#!/bin/sh\n{script}")).unwrap();
+		std::fs::set_permissions(&executable, std::fs::Permissions::from_mode(0o755)).unwrap();
+		let options = crate::options::Runtime {
+			executable,
+			args: vec![],
+		};
+		Runtime::new(server, options)
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn script() {
+		let server = TestServer::new().await;
+
+		// A script that finishes its build through the proxy server returns its output.
+		let arg = tg::build::finish::Arg {
+			outcome: tg::build::outcome::Data::Succeeded(tg::value::Data::String(
+				"output".to_owned(),
+			)),
+			remote: None,
+		};
+		let arg = serde_json::to_string(&arg).unwrap();
+		let script = formatdoc!(
+			r#"
+				echo "hello"
+				curl -sf --unix-socket socket -X POST -H "content-type: application/json" -d '{arg}' "http://localhost/builds/$TANGRAM_BUILD/finish"
+			"#
+		);
+		let runtime = script_runtime(&server, "finish", &script);
+		let build = server.put_started_build("script", vec![]).await;
+		let output = runtime.build(&build, None).await.unwrap();
+		assert_eq!(output.try_unwrap_string().unwrap(), "output");
+
+		// A script that exits successfully without finishing its build fails.
+		let runtime = script_runtime(&server, "exit", "exit 0");
+		let build = server.put_started_build("script", vec![]).await;
+		assert!(runtime.build(&build, None).await.is_err());
+
+		// A script that exits unsuccessfully fails.
+		let runtime = script_runtime(&server, "fail", "exit 1");
+		let build = server.put_started_build("script", vec![]).await;
+		assert!(runtime.build(&build, None).await.is_err());
+	}
+}
@@ -1,5 +1,5 @@
 use super::State;
-use std::rc::Rc;
+use std::{collections::BTreeSet, rc::Rc};
 use tangram_client as tg;
 
 pub async fn output(state: Rc<State>, args: (tg::Target,)) -> tg::Result<tg::Value> {
@@ -13,9 +13,12 @@ pub async fn output(state: Rc<State>, args: (tg::Target,)) -> tg::Result<tg::Val
 			let retry = parent.retry(&server).await?;
 			let arg = tg::target::build::Arg {
 				create: true,
+				labels: BTreeSet::new(),
 				parent: Some(parent.id().clone()),
+				priority: None,
 				remote,
 				retry,
+				timeout: None,
 			};
 			target.output(&server, arg).await
 		})
@@ -10,6 +10,7 @@ use futures::{
 };
 use indoc::formatdoc;
 use itertools::Itertools as _;
+use num::ToPrimitive as _;
 use std::{
 	collections::BTreeMap,
 	ffi::CString,
@@ -51,11 +52,18 @@ const ENV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/env_aarch64_linux")
 #[cfg(target_arch = "x86_64")]
 const ENV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/env_x86_64_linux"));
 
+/// The cgroup v2 mount point.
+const CGROUP_PATH: &str = "/sys/fs/cgroup";
+
+/// The period for the CPU quota, in microseconds.
+const CPU_PERIOD: u64 = 100_000;
+
 #[derive(Clone)]
 pub struct Runtime {
 	server: Server,
 	env: tg::File,
 	sh: tg::File,
+	cgroup: Option<PathBuf>,
 }
 
 impl Runtime {
@@ -64,8 +72,30 @@ impl Runtime {
 		let env = tg::File::builder(env).executable(true).build();
 		let sh = tg::Blob::with_reader(server, DASH).await?;
 		let sh = tg::File::builder(sh).executable(true).build();
+
+		// If any limits are set, then create a cgroup for builds. This moves the server's process into a new cgroup, so limits are only enforced if they are configured.
+		let limits = server
+			.options
+			.build
+			.as_ref()
+			.map(|options| options.limits.clone())
+			.unwrap_or_default();
+		let cgroup = if limits.cpu.is_some() || limits.memory.is_some() || limits.pids.is_some() {
+			let cgroup = create_builds_cgroup().await.map_err(|source| {
+				tg::error!(!source, "the build limits require a cgroup v2 hierarchy")
+			})?;
+			Some(cgroup)
+		} else {
+			None
+		};
+
 		let server = server.clone();
-		Ok(Self { server, env, sh })
+		Ok(Self {
+			server,
+			env,
+			sh,
+			cgroup,
+		})
 	}
 
 	pub async fn build(&self, build: &tg::Build, remote: Option<String>) -> tg::Result<tg::Value> {
@@ -198,7 +228,13 @@ impl Runtime {
 			Some(path_map),
 		);
 		let stop = Stop::new();
-		let proxy_task = tokio::spawn(Server::serve(proxy, proxy_server_host_url.clone(), stop));
+		let proxy_task = tokio::spawn(Server::serve(
+			proxy,
+			proxy_server_host_url.clone(),
+			None,
+			None,
+			stop,
+		));
 
 		// Render the executable.
 		let executable = target.executable(server).await?;
@@ -237,6 +273,21 @@ impl Runtime {
 		// Enable the network if a checksum was provided.
 		let network_enabled = target.checksum(server).await?.is_some();
 
+		// Get the limits.
+		let limits = server
+			.options
+			.build
+			.as_ref()
+			.map(|options| options.limits.clone())
+			.unwrap_or_default();
+
+		// Create the build's cgroup.
+		let cgroup = if let Some(parent) = &self.cgroup {
+			Some(Cgroup::new(parent, build.id(), &limits).await?)
+		} else {
+			None
+		};
+
 		// Set `$HOME`.
 		env.insert(
 			"HOME".to_owned(),
@@ -395,7 +446,7 @@ impl Runtime {
 			readonly: false,
 		});
 
-		// Add the server directory to the mounts.
+		// Add the server directory to the mounts. It is read-only so that builds cannot modify the server's data. The proxy server's socket is in the home directory, so it remains writable.
 		let server_directory_source_path = &server_directory_host_path;
 		let server_directory_target_path =
 			root_directory_host_path.join(server_directory_guest_path.strip_prefix("/").unwrap());
@@ -417,7 +468,7 @@ impl Runtime {
 			fstype: None,
 			flags: libc::MS_BIND | libc::MS_REC,
 			data: None,
-			readonly: false,
+			readonly: true,
 		});
 
 		// Add the home directory to the mounts.
@@ -464,6 +515,48 @@ impl Runtime {
 			readonly: false,
 		});
 
+		// Add the configured mounts. They make a build depend on the host, so like the network, they are only available to builds whose targets have checksums. Other builds fail instead of running without them.
+		let configured_mounts = server
+			.options
+			.build
+			.as_ref()
+			.map(|options| options.mounts.clone())
+			.unwrap_or_default();
+		if !configured_mounts.is_empty() && !network_enabled {
+			return Err(tg::error!(
+				"the server has build mounts, which are only available to builds whose targets have checksums"
+			));
+		}
+		for mount in configured_mounts {
+			let source_path = &mount.source;
+			let target_path = mount_target_path(&root_directory_host_path, &mount.target)?;
+			let metadata = tokio::fs::metadata(source_path).await.map_err(
+				|source| tg::error!(!source, %path = source_path.display(), "failed to get the metadata of the mount source"),
+			)?;
+			if metadata.is_dir() {
+				tokio::fs::create_dir_all(&target_path).await.map_err(
+					|source| tg::error!(!source, %path = target_path.display(), "failed to create the mount point"),
+				)?;
+			} else {
+				tokio::fs::create_dir_all(target_path.parent().unwrap())
+					.await
+					.map_err(|source| tg::error!(!source, %path = target_path.display(), "failed to create the mount point"))?;
+				tokio::fs::write(&target_path, "").await.map_err(
+					|source| tg::error!(!source, %path = target_path.display(), "failed to create the mount point"),
+				)?;
+			}
+			let source_path = CString::new(source_path.as_os_str().as_bytes()).unwrap();
+			let target_path = CString::new(target_path.as_os_str().as_bytes()).unwrap();
+			mounts.push(Mount {
+				source: source_path,
+				target: target_path,
+				fstype: None,
+				flags: libc::MS_BIND | libc::MS_REC,
+				data: None,
+				readonly: mount.readonly,
+			});
+		}
+
 		// Create the executable.
 		let executable = CString::new(executable)
 			.map_err(|source| tg::error!(!source, "the executable is not a valid C string"))?;
@@ -669,6 +762,12 @@ impl Runtime {
 		.await
 		.map_err(|source| tg::error!(!source, "failed to set the GID map"))?;
 
+		// Add the root and guest processes to the cgroup.
+		if let Some(cgroup) = &cgroup {
+			cgroup.add(root_process_pid).await?;
+			cgroup.add(guest_process_pid).await?;
+		}
+
 		// Notify the guest process that it can continue.
 		host_socket.write_u8(1).await.map_err(|error| {
 			tg::error!(
@@ -746,6 +845,13 @@ impl Runtime {
 			.map_err(|source| tg::error!(!source, "failed to join the log task"))?
 			.map_err(|source| tg::error!(!source, "the log task failed"))?;
 
+		// If the process failed and the cgroup reports that a limit was exceeded, then return an error naming the limit.
+		if let (Some(cgroup), false) = (&cgroup, exit_status == ExitStatus::Code(0)) {
+			if let Some(error) = cgroup.exceeded(&limits).await? {
+				return Err(error);
+			}
+		}
+
 		// Handle the guest process's exit status.
 		match exit_status {
 			ExitStatus::Code(0) => (),
@@ -853,7 +959,7 @@ fn root(context: &Context) {
 			abort_errno!("failed to close stdin");
 		}
 
-		// If network access is disabled, set CLONE_NEWNET to isolate the guest's network namespace.
+		// If network access is disabled, set CLONE_NEWNET to isolate the guest's network namespace. The guest brings up its loopback interface.
 		let network_clone_flags = if context.network_enabled {
 			0
 		} else {
@@ -971,6 +1077,31 @@ fn guest(context: &Context) {
 		}
 		assert_eq!(notification, 1);
 
+		// If network access is disabled, then bring up the loopback interface in the guest's network namespace, so that the guest can use loopback addresses while external networking remains unavailable.
+		if !context.network_enabled {
+			let socket = libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0);
+			if socket == -1 {
+				abort_errno!("failed to create a socket to configure the loopback interface");
+			}
+			let mut ifreq: libc::ifreq = std::mem::zeroed();
+			for (dst, src) in ifreq.ifr_name.iter_mut().zip(c"lo".to_bytes()) {
+				*dst = libc::c_char::from_ne_bytes([*src]);
+			}
+			let ret = libc::ioctl(socket, libc::SIOCGIFFLAGS, std::ptr::addr_of_mut!(ifreq));
+			if ret == -1 {
+				abort_errno!("failed to get the loopback interface's flags");
+			}
+			let Ok(flags) = libc::c_short::try_from(libc::IFF_UP | libc::IFF_RUNNING) else {
+				abort!("invalid interface flags");
+			};
+			ifreq.ifr_ifru.ifru_flags |= flags;
+			let ret = libc::ioctl(socket, libc::SIOCSIFFLAGS, std::ptr::addr_of_mut!(ifreq));
+			if ret == -1 {
+				abort_errno!("failed to bring up the loopback interface");
+			}
+			libc::close(socket);
+		}
+
 		// Perform the mounts.
 		for mount in &context.mounts {
 			let source = mount.source.as_ptr();
@@ -1069,6 +1200,177 @@ fn guest(context: &Context) {
 	}
 }
 
+/// Get the host path of a mount's target in the root. The target must be an absolute path with only normal components, so that it cannot leave the root.
+fn mount_target_path(root: &Path, target: &Path) -> tg::Result<PathBuf> {
+	let mut components = target.components();
+	if components.next() != Some(std::path::Component::RootDir) {
+		return Err(tg::error!(
+			%path = target.display(),
+			"the mount target must be an absolute path"
+		));
+	}
+	let mut path = root.to_owned();
+	for component in components {
+		let std::path::Component::Normal(component) = component else {
+			return Err(tg::error!(
+				%path = target.display(),
+				"the mount target must not contain parent directory components"
+			));
+		};
+		path.push(component);
+	}
+	Ok(path)
+}
+
+/// Create a cgroup for builds with the cpu, memory, and pids controllers enabled.
+///
+/// The server's process is moved from its current cgroup to a new leaf cgroup named `server`, because a cgroup with processes cannot enable controllers for its children. Builds' cgroups are then created in a sibling cgroup named `builds`. The server's process must be allowed to write to its cgroup, for example by running it in a delegated systemd unit.
+async fn create_builds_cgroup() -> tg::Result<PathBuf> {
+	// Get the server's cgroup.
+	let contents = tokio::fs::read_to_string("/proc/self/cgroup")
+		.await
+		.map_err(|source| tg::error!(!source, "failed to read the cgroup"))?;
+	let path = contents
+		.lines()
+		.find_map(|line| line.strip_prefix("0::"))
+		.ok_or_else(|| tg::error!("expected a cgroup v2 hierarchy"))?;
+	let cgroup = Path::new(CGROUP_PATH).join(path.trim_start_matches('/'));
+
+	// Move the server's process to a leaf cgroup.
+	let server = cgroup.join("server");
+	tokio::fs::create_dir_all(&server)
+		.await
+		.map_err(|source| tg::error!(!source, "failed to create the server cgroup"))?;
+	tokio::fs::write(server.join("cgroup.procs"), std::process::id().to_string())
+		.await
+		.map_err(|source| tg::error!(!source, "failed to move the server to its cgroup"))?;
+	tracing::info!(path = %server.display(), "moved the server's process to a new cgroup to enforce the build limits");
+
+	// Enable the controllers.
+	tokio::fs::write(cgroup.join("cgroup.subtree_control"), "+cpu +memory +pids")
+		.await
+		.map_err(|source| tg::error!(!source, "failed to enable the cgroup controllers"))?;
+
+	// Create the builds cgroup.
+	let builds = cgroup.join("builds");
+	tokio::fs::create_dir_all(&builds)
+		.await
+		.map_err(|source| tg::error!(!source, "failed to create the builds cgroup"))?;
+	tokio::fs::write(builds.join("cgroup.subtree_control"), "+cpu +memory +pids")
+		.await
+		.map_err(|source| tg::error!(!source, "failed to enable the cgroup controllers"))?;
+
+	Ok(builds)
+}
+
+/// A cgroup for a single build. When it is dropped, its processes are killed and it is removed.
+struct Cgroup {
+	path: PathBuf,
+}
+
+impl Cgroup {
+	async fn new(
+		parent: &Path,
+		build: &tg::build::Id,
+		limits: &crate::options::BuildLimits,
+	) -> tg::Result<Self> {
+		let path = parent.join(build.to_string());
+		tokio::fs::create_dir_all(&path).await.map_err(
+			|source| tg::error!(!source, %path = path.display(), "failed to create the cgroup"),
+		)?;
+		let cgroup = Self { path };
+		if let Some(cpu) = limits.cpu {
+			let quota = cpu_quota(cpu)?;
+			cgroup
+				.write("cpu.max", &format!("{quota} {CPU_PERIOD}"))
+				.await?;
+		}
+		if let Some(memory) = limits.memory {
+			cgroup.write("memory.max", &memory.to_string()).await?;
+		}
+		if let Some(pids) = limits.pids {
+			cgroup.write("pids.max", &pids.to_string()).await?;
+		}
+		Ok(cgroup)
+	}
+
+	async fn add(&self, pid: libc::pid_t) -> tg::Result<()> {
+		self.write("cgroup.procs", &pid.to_string()).await
+	}
+
+	/// Get an error naming the limit that was exceeded, if any.
+	async fn exceeded(
+		&self,
+		limits: &crate::options::BuildLimits,
+	) -> tg::Result<Option<tg::Error>> {
+		if let Some(memory) = limits.memory {
+			if self.event("memory.events", "oom_kill").await? > 0 {
+				return Ok(Some(
+					tg::error!(%memory, "the build exceeded the memory limit"),
+				));
+			}
+		}
+		if let Some(pids) = limits.pids {
+			if self.event("pids.events", "max").await? > 0 {
+				return Ok(Some(
+					tg::error!(%pids, "the build exceeded the process limit"),
+				));
+			}
+		}
+		Ok(None)
+	}
+
+	async fn event(&self, file: &str, name: &str) -> tg::Result<u64> {
+		let path = self.path.join(file);
+		let contents = tokio::fs::read_to_string(&path).await.map_err(
+			|source| tg::error!(!source, %path = path.display(), "failed to read the cgroup events"),
+		)?;
+		let count = contents
+			.lines()
+			.find_map(|line| {
+				let (key, value) = line.split_once(' ')?;
+				(key == name).then(|| value.parse().ok()).flatten()
+			})
+			.unwrap_or(0);
+		Ok(count)
+	}
+
+	async fn write(&self, file: &str, contents: &str) -> tg::Result<()> {
+		let path = self.path.join(file);
+		tokio::fs::write(&path, contents).await.map_err(
+			|source| tg::error!(!source, %path = path.display(), "failed to write to the cgroup"),
+		)
+	}
+}
+
+/// Get the CPU quota in microseconds per period for a number of CPUs.
+fn cpu_quota(cpu: f64) -> tg::Result<u64> {
+	let quota = (cpu * CPU_PERIOD.to_f64().unwrap())
+		.to_u64()
+		.filter(|quota| *quota > 0)
+		.ok_or_else(|| tg::error!(%cpu, "invalid cpu limit"))?;
+	Ok(quota)
+}
+
+impl Drop for Cgroup {
+	fn drop(&mut self) {
+		// Kill the cgroup's processes.
+		std::fs::write(self.path.join("cgroup.kill"), "1").ok();
+
+		// Remove the cgroup once its processes have exited.
+		let path = self.path.clone();
+		tokio::spawn(async move {
+			for _ in 0..10 {
+				if tokio::fs::remove_dir(&path).await.is_ok() {
+					return;
+				}
+				tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+			}
+			tracing::warn!(path = %path.display(), "failed to remove the cgroup");
+		});
+	}
+}
+
 struct CStringVec {
 	_strings: Vec<CString>,
 	pointers: Vec<*const libc::c_char>,
@@ -1117,3 +1419,36 @@ macro_rules! abort_errno {
 }
 
 use abort_errno;
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_cpu_quota() {
+		assert_eq!(cpu_quota(1.0).unwrap(), CPU_PERIOD);
+		assert_eq!(cpu_quota(0.5).unwrap(), CPU_PERIOD / 2);
+		assert_eq!(cpu_quota(2.0).unwrap(), 2 * CPU_PERIOD);
+		assert!(cpu_quota(0.0).is_err());
+		assert!(cpu_quota(-1.0).is_err());
+		assert!(cpu_quota(f64::NAN).is_err());
+		assert!(cpu_quota(f64::INFINITY).is_err());
+		assert!(cpu_quota(f64::MAX).is_err());
+	}
+
+	#[test]
+	fn test_mount_target_path() {
+		let root = Path::new("/root");
+		assert_eq!(
+			mount_target_path(root, Path::new("/a/b")).unwrap(),
+			Path::new("/root/a/b")
+		);
+		assert_eq!(
+			mount_target_path(root, Path::new("/a/./b/")).unwrap(),
+			Path::new("/root/a/b")
+		);
+		assert!(mount_target_path(root, Path::new("a/b")).is_err());
+		assert!(mount_target_path(root, Path::new("/a/../b")).is_err());
+		assert!(mount_target_path(root, Path::new("/../etc")).is_err());
+	}
+}
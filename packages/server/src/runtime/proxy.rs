@@ -10,6 +10,7 @@ pub struct Proxy(Arc<Inner>);
 
 pub struct Inner {
 	build: tg::build::Id,
+	outcome: std::sync::Mutex<Option<tg::build::outcome::Data>>,
 	path_map: Option<PathMap>,
 	remote: Option<String>,
 	server: Server,
@@ -31,6 +32,7 @@ impl Proxy {
 	) -> Self {
 		let inner = Inner {
 			build,
+			outcome: std::sync::Mutex::new(None),
 			path_map,
 			remote,
 			server,
@@ -38,6 +40,11 @@ impl Proxy {
 		Self(Arc::new(inner))
 	}
 
+	/// Take the outcome that the build's process finished the build with, if any.
+	pub fn take_outcome(&self) -> Option<tg::build::outcome::Data> {
+		self.outcome.lock().unwrap().take()
+	}
+
 	fn host_path_for_guest_path(&self, path: PathBuf) -> tg::Result<PathBuf> {
 		// Get the path map. If there is no path map, then the guest path is the host path.
 		let Some(path_map) = &self.path_map else {
@@ -152,6 +159,10 @@ impl tg::Handle for Proxy {
 		self.server.try_read_blob_stream(id, arg)
 	}
 
+	async fn list_builds(&self, _arg: tg::build::list::Arg) -> tg::Result<tg::build::list::Output> {
+		Err(tg::error!("forbidden"))
+	}
+
 	fn try_get_build(
 		&self,
 		id: &tg::build::Id,
@@ -258,10 +269,15 @@ impl tg::Handle for Proxy {
 
 	async fn finish_build(
 		&self,
-		_id: &tg::build::Id,
-		_arg: tg::build::finish::Arg,
+		id: &tg::build::Id,
+		arg: tg::build::finish::Arg,
 	) -> tg::Result<bool> {
-		Err(tg::error!("forbidden"))
+		// The build's process may only finish its own build. The outcome is stored, so that the runtime can finish the build once the process exits.
+		if id != &self.build {
+			return Err(tg::error!("forbidden"));
+		}
+		self.outcome.lock().unwrap().replace(arg.outcome);
+		Ok(true)
 	}
 
 	async fn touch_build(
@@ -370,6 +386,22 @@ impl tg::Handle for Proxy {
 		Err(tg::error!("forbidden"))
 	}
 
+	async fn list_roots(&self, _arg: tg::root::list::Arg) -> tg::Result<tg::root::list::Output> {
+		Err(tg::error!("forbidden"))
+	}
+
+	async fn try_get_root(&self, _name: &str) -> tg::Result<Option<tg::root::get::Output>> {
+		Err(tg::error!("forbidden"))
+	}
+
+	async fn put_root(&self, _name: &str, _arg: tg::root::put::Arg) -> tg::Result<()> {
+		Err(tg::error!("forbidden"))
+	}
+
+	async fn delete_root(&self, _name: &str) -> tg::Result<()> {
+		Err(tg::error!("forbidden"))
+	}
+
 	async fn get_js_runtime_doc(&self) -> tg::Result<serde_json::Value> {
 		Err(tg::error!("forbidden"))
 	}
@@ -378,7 +410,7 @@ impl tg::Handle for Proxy {
 		Err(tg::error!("forbidden"))
 	}
 
-	async fn clean(&self) -> tg::Result<()> {
+	async fn clean(&self, _arg: tg::server::clean::Arg) -> tg::Result<tg::server::clean::Output> {
 		Err(tg::error!("forbidden"))
 	}
 
@@ -412,9 +444,37 @@ impl tg::Handle for Proxy {
 		self.server.try_build_target(id, arg).await
 	}
 
+	async fn explain_target(
+		&self,
+		_id: &tg::target::Id,
+		_arg: tg::target::explain::Arg,
+	) -> tg::Result<tg::target::explain::Output> {
+		Err(tg::error!("forbidden"))
+	}
+
+	async fn create_login(&self) -> tg::Result<tg::Login> {
+		Err(tg::error!("forbidden"))
+	}
+
+	async fn try_get_login(
+		&self,
+		_id: &tg::login::Id,
+		_arg: tg::login::get::Arg,
+	) -> tg::Result<Option<tg::Login>> {
+		Err(tg::error!("forbidden"))
+	}
+
+	async fn finish_login(&self, _arg: tg::login::finish::Arg) -> tg::Result<()> {
+		Err(tg::error!("forbidden"))
+	}
+
 	async fn get_user(&self, _token: &str) -> tg::Result<Option<tg::User>> {
 		Err(tg::error!("forbidden"))
 	}
+
+	async fn delete_token(&self, _token: &str) -> tg::Result<()> {
+		Err(tg::error!("forbidden"))
+	}
 }
 
 impl std::ops::Deref for Proxy {
@@ -424,3 +484,38 @@ impl std::ops::Deref for Proxy {
 		&self.0
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::TestServer;
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn finish_build() {
+		let server = TestServer::new().await;
+		let build = tg::build::Id::new();
+		let proxy = Proxy::new(server.clone(), build.clone(), None, None);
+
+		// Finishing another build is forbidden.
+		let arg = tg::build::finish::Arg {
+			outcome: tg::build::outcome::Data::Canceled,
+			remote: None,
+		};
+		assert!(tg::Handle::finish_build(&proxy, &tg::build::Id::new(), arg)
+			.await
+			.is_err());
+		assert!(proxy.take_outcome().is_none());
+
+		// Finishing the proxy's build stores the outcome.
+		let arg = tg::build::finish::Arg {
+			outcome: tg::build::outcome::Data::Succeeded(tg::value::Data::Null),
+			remote: None,
+		};
+		assert!(tg::Handle::finish_build(&proxy, &build, arg).await.unwrap());
+		assert!(matches!(
+			proxy.take_outcome(),
+			Some(tg::build::outcome::Data::Succeeded(tg::value::Data::Null))
+		));
+		assert!(proxy.take_outcome().is_none());
+	}
+}
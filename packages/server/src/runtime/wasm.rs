@@ -0,0 +1,313 @@
+use super::util::render;
+use crate::{tmp::Tmp, Server};
+use futures::{
+	stream::{self, FuturesOrdered, FuturesUnordered},
+	StreamExt as _, TryStreamExt as _,
+};
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc, time::Duration};
+use tangram_client as tg;
+use tokio::io::AsyncWriteExt as _;
+use tokio_util::{io::ReaderStream, task::AbortOnDropHandle};
+use wasmtime_wasi::{
+	pipe::AsyncWriteStream, preview1::WasiP1Ctx, AsyncStdoutStream, DirPerms, FilePerms,
+};
+
+/// The artifacts directory guest path.
+const ARTIFACTS_DIRECTORY_GUEST_PATH: &str = "/.tangram/artifacts";
+
+/// The home directory guest path.
+const HOME_DIRECTORY_GUEST_PATH: &str = "/home/tangram";
+
+/// The output parent directory guest path.
+const OUTPUT_PARENT_DIRECTORY_GUEST_PATH: &str = "/output";
+
+/// The working directory guest path.
+const WORKING_DIRECTORY_GUEST_PATH: &str = "/home/tangram/work";
+
+/// The interval at which running modules yield to the async runtime, so that builds can be canceled.
+const EPOCH_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The number of bytes of output a module may buffer before its writes block.
+const WRITE_BUDGET: usize = 1 << 16;
+
+#[derive(Clone)]
+pub struct Runtime {
+	server: Server,
+	engine: wasmtime::Engine,
+	#[allow(dead_code)]
+	epoch_task: Arc<AbortOnDropHandle<()>>,
+}
+
+impl Runtime {
+	pub fn new(server: &Server) -> tg::Result<Self> {
+		// Create the engine.
+		let mut config = wasmtime::Config::new();
+		config.async_support(true);
+		config.epoch_interruption(true);
+		let engine = wasmtime::Engine::new(&config)
+			.map_err(|source| tg::error!(!source, "failed to create the wasm engine"))?;
+
+		// Spawn a task to increment the engine's epoch. The task is aborted when the last clone of the runtime is dropped, which happens when the server stops and removes its runtimes.
+		let epoch_task = tokio::spawn({
+			let engine = engine.clone();
+			async move {
+				let mut interval = tokio::time::interval(EPOCH_INTERVAL);
+				loop {
+					interval.tick().await;
+					engine.increment_epoch();
+				}
+			}
+		});
+		let epoch_task = Arc::new(AbortOnDropHandle::new(epoch_task));
+
+		Ok(Self {
+			server: server.clone(),
+			engine,
+			epoch_task,
+		})
+	}
+
+	pub async fn build(&self, build: &tg::Build, remote: Option<String>) -> tg::Result<tg::Value> {
+		let server = &self.server;
+
+		// Get the target.
+		let target = build.target(server).await?;
+
+		// If the VFS is disabled, then check out the target's children.
+		if server.vfs.lock().unwrap().is_none() {
+			target
+				.data(server)
+				.await?
+				.children()
+				.into_iter()
+				.filter_map(|id| id.try_into().ok())
+				.map(tg::Artifact::with_id)
+				.map(|artifact| async move {
+					let arg = tg::artifact::checkout::Arg::default();
+					artifact.check_out(server, arg).await?;
+					Ok::<_, tg::Error>(())
+				})
+				.collect::<FuturesUnordered<_>>()
+				.try_collect::<Vec<_>>()
+				.await?;
+		}
+
+		// Get the module.
+		let executable = target
+			.executable(server)
+			.await?
+			.clone()
+			.ok_or_else(|| tg::error!("the target must have an executable"))?;
+		let file = executable
+			.try_unwrap_file_ref()
+			.ok()
+			.ok_or_else(|| tg::error!("the executable must be a file"))?;
+		let bytes = file.contents(server).await?.bytes(server).await?;
+
+		// Compile the module in a blocking task.
+		let module = tokio::task::spawn_blocking({
+			let engine = self.engine.clone();
+			move || wasmtime::Module::new(&engine, bytes)
+		})
+		.await
+		.unwrap()
+		.map_err(|source| tg::error!(!source, "failed to compile the module"))?;
+
+		// Create the guest paths.
+		let artifacts_directory_guest_path = PathBuf::from(ARTIFACTS_DIRECTORY_GUEST_PATH);
+		let output_parent_directory_guest_path = PathBuf::from(OUTPUT_PARENT_DIRECTORY_GUEST_PATH);
+		let output_guest_path = output_parent_directory_guest_path.join("output");
+
+		// Create a tempdir for the home directory.
+		let home_directory_tmp = Tmp::new(server);
+		let home_directory_host_path = PathBuf::from(home_directory_tmp.as_ref());
+		let working_directory_host_path = home_directory_host_path.join("work");
+		tokio::fs::create_dir_all(&working_directory_host_path)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to create the working directory"))?;
+
+		// Create a tempdir for the output.
+		let output_parent_directory_tmp = Tmp::new(server);
+		let output_parent_directory_host_path = PathBuf::from(output_parent_directory_tmp.as_ref());
+		tokio::fs::create_dir_all(&output_parent_directory_host_path)
+			.await
+			.map_err(|source| {
+				tg::error!(!source, "failed to create the output parent directory")
+			})?;
+		let output_host_path = output_parent_directory_host_path.join("output");
+
+		// Render the env.
+		let env = target.env(server).await?;
+		let mut env: BTreeMap<String, String> = env
+			.iter()
+			.map(|(key, value)| async {
+				let key = key.clone();
+				let value = render(server, value, &artifacts_directory_guest_path).await?;
+				Ok::<_, tg::Error>((key, value))
+			})
+			.collect::<FuturesOrdered<_>>()
+			.try_collect()
+			.await?;
+
+		// Render the args.
+		let args = target.args(server).await?;
+		let mut args: Vec<String> = args
+			.iter()
+			.map(|value| async {
+				let value = render(server, value, &artifacts_directory_guest_path).await?;
+				Ok::<_, tg::Error>(value)
+			})
+			.collect::<FuturesOrdered<_>>()
+			.try_collect()
+			.await?;
+
+		// The first arg is the module's path.
+		let executable_guest_path =
+			artifacts_directory_guest_path.join(executable.id(server).await?.to_string());
+		args.insert(0, executable_guest_path.to_str().unwrap().to_owned());
+
+		// Set `$HOME`.
+		env.insert("HOME".to_owned(), HOME_DIRECTORY_GUEST_PATH.to_owned());
+
+		// Set `$OUTPUT`.
+		env.insert(
+			"OUTPUT".to_owned(),
+			output_guest_path.to_str().unwrap().to_owned(),
+		);
+
+		// Set `$PWD`.
+		env.insert("PWD".to_owned(), WORKING_DIRECTORY_GUEST_PATH.to_owned());
+
+		// Create the pipes for stdout and stderr.
+		let (stdout_send, stdout_recv) = tokio::io::duplex(WRITE_BUDGET);
+		let stdout = AsyncStdoutStream::new(AsyncWriteStream::new(WRITE_BUDGET, stdout_send));
+		let (stderr_send, stderr_recv) = tokio::io::duplex(WRITE_BUDGET);
+		let stderr = AsyncStdoutStream::new(AsyncWriteStream::new(WRITE_BUDGET, stderr_send));
+
+		// Create the WASI context. The artifacts directory is read only, and the network is not available.
+		let env = env.into_iter().collect::<Vec<_>>();
+		let ctx = wasmtime_wasi::WasiCtxBuilder::new()
+			.args(&args)
+			.envs(&env)
+			.stdout(stdout)
+			.stderr(stderr)
+			.preopened_dir(
+				server.artifacts_path(),
+				ARTIFACTS_DIRECTORY_GUEST_PATH,
+				DirPerms::READ,
+				FilePerms::READ,
+			)
+			.and_then(|builder| {
+				builder.preopened_dir(
+					&home_directory_host_path,
+					HOME_DIRECTORY_GUEST_PATH,
+					DirPerms::all(),
+					FilePerms::all(),
+				)
+			})
+			.and_then(|builder| {
+				builder.preopened_dir(
+					&output_parent_directory_host_path,
+					OUTPUT_PARENT_DIRECTORY_GUEST_PATH,
+					DirPerms::all(),
+					FilePerms::all(),
+				)
+			})
+			.map_err(|source| tg::error!(!source, "failed to open a preopened directory"))?
+			.build_p1();
+
+		// Spawn the log task.
+		let log_task = tokio::task::spawn({
+			let server = server.clone();
+			let build = build.clone();
+			let remote = remote.clone();
+			async move {
+				let mut stream = stream::select(
+					ReaderStream::new(stdout_recv),
+					ReaderStream::new(stderr_recv),
+				);
+				while let Some(bytes) = stream.next().await {
+					let bytes = bytes
+						.map_err(|source| tg::error!(!source, "failed to read from the log"))?;
+					if server.options.advanced.write_build_logs_to_stderr {
+						tokio::io::stderr()
+							.write_all(&bytes)
+							.await
+							.inspect_err(|error| {
+								tracing::error!(?error, "failed to write the build log to stderr");
+							})
+							.ok();
+					}
+					let arg = tg::build::log::post::Arg {
+						bytes,
+						remote: remote.clone(),
+					};
+					build.add_log(&server, arg).await?;
+				}
+				Ok::<_, tg::Error>(())
+			}
+		});
+
+		// Instantiate and run the module.
+		let mut linker = wasmtime::Linker::<WasiP1Ctx>::new(&self.engine);
+		wasmtime_wasi::preview1::add_to_linker_async(&mut linker, |ctx| ctx)
+			.map_err(|source| tg::error!(!source, "failed to add WASI to the linker"))?;
+		let mut store = wasmtime::Store::new(&self.engine, ctx);
+		store.epoch_deadline_async_yield_and_update(1);
+		let result = async {
+			linker
+				.module_async(&mut store, "", &module)
+				.await?
+				.get_default(&mut store, "")?
+				.typed::<(), ()>(&store)?
+				.call_async(&mut store, ())
+				.await
+		}
+		.await;
+
+		// Drop the store to close the module's stdout and stderr.
+		drop(store);
+
+		// Wait for the log task to complete.
+		log_task
+			.await
+			.map_err(|source| tg::error!(!source, "failed to join the log task"))?
+			.map_err(|source| tg::error!(!source, "the log task failed"))?;
+
+		// Handle the module's exit status.
+		if let Err(error) = result {
+			match error.downcast_ref::<wasmtime_wasi::I32Exit>() {
+				Some(wasmtime_wasi::I32Exit(0)) => (),
+				Some(wasmtime_wasi::I32Exit(code)) => {
+					return Err(tg::error!(r#"the process exited with code "{code}""#));
+				},
+				None => {
+					let error = format!("{error:#}");
+					return Err(tg::error!(%error, "the module trapped"));
+				},
+			}
+		}
+
+		// Create the output.
+		let value = if tokio::fs::try_exists(&output_host_path)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to determine in the path exists"))?
+		{
+			let arg = tg::artifact::checkin::Arg {
+				destructive: true,
+				deterministic: true,
+				ignore: false,
+				path: output_host_path.clone(),
+				locked: true,
+			};
+			tg::Artifact::check_in(server, arg)
+				.await
+				.map_err(|source| tg::error!(!source, "failed to check in the output"))?
+				.into()
+		} else {
+			tg::Value::Null
+		};
+
+		Ok(value)
+	}
+}
@@ -3,7 +3,7 @@ use num::ToPrimitive;
 use tangram_client as tg;
 use tangram_database::{self as db, prelude::*};
 use tangram_either::Either;
-use tangram_http::{outgoing::response::Ext as _, Incoming, Outgoing};
+use tangram_http::{incoming::request::Ext as _, outgoing::response::Ext as _, Incoming, Outgoing};
 
 impl Server {
 	pub async fn health(&self) -> tg::Result<tg::server::Health> {
@@ -76,13 +76,15 @@ impl Server {
 impl Server {
 	pub(crate) async fn handle_server_clean_request<H>(
 		handle: &H,
-		_request: http::Request<Incoming>,
+		request: http::Request<Incoming>,
 	) -> tg::Result<http::Response<Outgoing>>
 	where
 		H: tg::Handle,
 	{
-		handle.clean().await?;
-		Ok(http::Response::builder().empty().unwrap())
+		let arg = request.json().await?;
+		let output = handle.clean(arg).await?;
+		let response = http::Response::builder().json(output).unwrap();
+		Ok(response)
 	}
 
 	pub(crate) async fn handle_server_health_request<H>(
@@ -1 +1,2 @@
 mod build;
+mod explain;
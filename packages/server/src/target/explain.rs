@@ -0,0 +1,125 @@
+use crate::Server;
+use futures::{future, FutureExt as _};
+use itertools::Itertools as _;
+use tangram_client as tg;
+use tangram_http::{incoming::request::Ext as _, outgoing::response::Ext as _, Incoming, Outgoing};
+
+impl Server {
+	pub async fn explain_target(
+		&self,
+		id: &tg::target::Id,
+		arg: tg::target::explain::Arg,
+	) -> tg::Result<tg::target::explain::Output> {
+		// Get the target's local builds.
+		let list_arg = tg::build::list::Arg {
+			target: Some(id.clone()),
+			..Default::default()
+		};
+		let builds = self.list_builds(list_arg).await?.data;
+
+		// Determine whether the most recent local build would be used, the same way that building the target does.
+		let reason = if let Some(build) = builds.first() {
+			let outcome = build.outcome.as_ref().map(|outcome| match outcome {
+				tg::build::outcome::Data::Canceled => tg::build::Retry::Canceled,
+				tg::build::outcome::Data::Failed(_) => tg::build::Retry::Failed,
+				tg::build::outcome::Data::Succeeded(_) => tg::build::Retry::Succeeded,
+			});
+			match outcome {
+				Some(outcome)
+					if build.status == tg::build::Status::Finished && outcome <= arg.retry =>
+				{
+					format!(
+						"the most recent local build {} finished with the outcome {outcome}, which the retry {} does not accept",
+						build.id, arg.retry
+					)
+				},
+				_ => {
+					let decision = tg::target::explain::Decision::Local {
+						build: build.id.clone(),
+					};
+					let reason = if build.status == tg::build::Status::Finished {
+						format!(
+							"the most recent local build {} is finished and its outcome satisfies the retry {}",
+							build.id, arg.retry
+						)
+					} else {
+						format!(
+							"the most recent local build {} is {}",
+							build.id, build.status
+						)
+					};
+					return Ok(tg::target::explain::Output {
+						builds,
+						decision,
+						reason,
+					});
+				},
+			}
+		} else {
+			"there are no local builds of the target".to_owned()
+		};
+
+		// Determine whether a remote build would be used. The first remote to respond with a build wins.
+		let futures = self
+			.remotes
+			.iter()
+			.map(|remote| {
+				let client = remote.value().clone();
+				let name = remote.key().clone();
+				let arg = tg::target::build::Arg {
+					create: false,
+					retry: arg.retry,
+					..Default::default()
+				};
+				async move {
+					let output = client
+						.try_build_target(id, arg)
+						.await?
+						.ok_or_else(|| tg::error!("the remote does not have a build"))?;
+					Ok::<_, tg::Error>((output.build, name))
+				}
+				.boxed()
+			})
+			.collect_vec();
+		if !futures.is_empty() {
+			if let Ok(((build, remote), _)) = future::select_ok(futures).await {
+				let reason = format!("{reason}, and the remote {remote} has the build {build}");
+				let decision = tg::target::explain::Decision::Remote { build, remote };
+				return Ok(tg::target::explain::Output {
+					builds,
+					decision,
+					reason,
+				});
+			}
+		}
+
+		// Otherwise, a new build would be created.
+		let reason = if self.remotes.is_empty() {
+			format!("{reason}, so a new build would be created")
+		} else {
+			format!("{reason}, and no remote has a build that satisfies the retry, so a new build would be created")
+		};
+		Ok(tg::target::explain::Output {
+			builds,
+			decision: tg::target::explain::Decision::New,
+			reason,
+		})
+	}
+}
+
+impl Server {
+	pub(crate) async fn handle_explain_target_request<H>(
+		handle: &H,
+		request: http::Request<Incoming>,
+		id: &str,
+	) -> tg::Result<http::Response<Outgoing>>
+	where
+		H: tg::Handle,
+	{
+		let id = id.parse()?;
+		let arg = request.query_params().transpose()?.unwrap_or_default();
+		let output = handle.explain_target(&id, arg).await?;
+		let response = http::Response::builder().json(output).unwrap();
+		Ok(response)
+	}
+}
@@ -0,0 +1,76 @@
+use crate::{Options, Server};
+use std::{collections::BTreeSet, path::Path};
+use tangram_client as tg;
+
+/// A server in a temporary directory for tests, which is stopped when it is dropped.
+pub(crate) struct TestServer {
+	server: Server,
+	temp: tempfile::TempDir,
+}
+
+impl TestServer {
+	pub(crate) async fn new() -> Self {
+		Self::with_options(|_| ()).await
+	}
+
+	/// Start a test server after applying `f` to its options.
+	pub(crate) async fn with_options(f: impl FnOnce(&mut Options)) -> Self {
+		let temp = tempfile::tempdir().unwrap();
+		let mut options = Options::test(temp.path().join("server"));
+		f(&mut options);
+		let server = Server::start(options).await.unwrap();
+		Self { server, temp }
+	}
+
+	/// Put a started build of a target with a host and args, as a runtime receives it.
+	pub(crate) async fn put_started_build(&self, host: &str, args: Vec<tg::Value>) -> tg::Build {
+		let target = tg::Target::builder(host).args(args).build();
+		let target = target.id(&self.server).await.unwrap();
+		let id = tg::build::Id::new();
+		let arg = tg::build::put::Arg {
+			id: id.clone(),
+			children: vec![],
+			host: host.to_owned(),
+			labels: BTreeSet::new(),
+			log: None,
+			outcome: None,
+			priority: 0,
+			retry: tg::build::Retry::Failed,
+			root: None,
+			status: tg::build::Status::Started,
+			target,
+			timeout: None,
+			created_at: time::OffsetDateTime::now_utc(),
+			dequeued_at: None,
+			started_at: None,
+			finished_at: None,
+		};
+		self.server.put_build(&id, arg).await.unwrap();
+		tg::Build::with_id(id)
+	}
+
+	/// Get the path of the temporary directory, which can hold files for the test alongside the server's directory.
+	pub(crate) fn temp_path(&self) -> &Path {
+		self.temp.path()
+	}
+}
+
+impl std::ops::Deref for TestServer {
+	type Target = Server;
+
+	fn deref(&self) -> &Self::Target {
+		&self.server
+	}
+}
+
+impl Drop for TestServer {
+	fn drop(&mut self) {
+		// Stop the server and wait for it, which requires the test to use the multi-threaded runtime.
+		self.server.stop();
+		tokio::task::block_in_place(|| {
+			tokio::runtime::Handle::current()
+				.block_on(self.server.wait())
+				.ok();
+		});
+	}
+}
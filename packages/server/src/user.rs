@@ -14,25 +14,79 @@ impl Server {
 			.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
 
 		// Get the user for the token.
+		#[derive(serde::Deserialize)]
+		struct Row {
+			id: tg::user::Id,
+			email: String,
+			scopes: String,
+		}
 		let p = connection.p();
 		let statement = formatdoc!(
-			"
-				select users.id, users.email, tokens.token
+			r#"
+				select users.id, users.email, tokens.scopes
 				from users
-				join tokens on tokens.user = users.id
-				where tokens.token = {p}1;
+				join tokens on tokens."user" = users.id
+				where tokens.id = {p}1;
+			"#
+		);
+		let params = db::params![token];
+		let row = connection
+			.query_optional_into::<Row>(statement, params)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
+
+		// Drop the database connection.
+		drop(connection);
+
+		// Create the user. The token's scopes are stored as a space separated list. Unknown scopes are skipped, so that a token with a scope from a newer version does not fail to authenticate.
+		let Some(row) = row else {
+			return Ok(None);
+		};
+		let scopes = row
+			.scopes
+			.split_whitespace()
+			.filter_map(|scope| {
+				scope
+					.parse()
+					.inspect_err(|error| tracing::warn!(?error, "skipping an unknown token scope"))
+					.ok()
+			})
+			.collect();
+		let user = tg::User {
+			id: row.id,
+			email: row.email,
+			scopes,
+		};
+
+		Ok(Some(user))
+	}
+
+	pub async fn delete_token(&self, token: &str) -> tg::Result<()> {
+		// Get a database connection.
+		let connection = self
+			.database
+			.connection(db::Priority::Low)
+			.await
+			.map_err(|source| tg::error!(!source, "failed to get a database connection"))?;
+
+		// Delete the token.
+		let p = connection.p();
+		let statement = formatdoc!(
+			"
+				delete from tokens
+				where id = {p}1;
 			"
 		);
 		let params = db::params![token];
-		let user = connection
-			.query_optional_into(statement, params)
+		connection
+			.execute(statement, params)
 			.await
 			.map_err(|source| tg::error!(!source, "failed to execute the statement"))?;
 
 		// Drop the database connection.
 		drop(connection);
 
-		Ok(user)
+		Ok(())
 	}
 }
 
@@ -55,6 +109,25 @@ impl Server {
 		Ok(response)
 	}
 
+	pub(crate) async fn handle_delete_token_request<H>(
+		handle: &H,
+		request: http::Request<Incoming>,
+	) -> tg::Result<http::Response<Outgoing>>
+	where
+		H: tg::Handle,
+	{
+		let Some(token) = request.token(None) else {
+			let response = http::Response::builder()
+				.status(http::StatusCode::UNAUTHORIZED)
+				.empty()
+				.unwrap();
+			return Ok(response);
+		};
+		handle.delete_token(token).await?;
+		let response = http::Response::builder().empty().unwrap();
+		Ok(response)
+	}
+
 	async fn try_get_user_from_request<H>(
 		handle: &H,
 		request: &http::Request<Incoming>,
@@ -75,3 +148,49 @@ impl Server {
 		Ok(Some(user))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::TestServer;
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn token() {
+		let server = TestServer::new().await;
+
+		// Create a user with a token that has an unknown scope.
+		let user = tg::user::Id::new();
+		let token = tg::Id::new_uuidv7(tg::id::Kind::Token).to_string();
+		let connection = server.database.connection(db::Priority::Low).await.unwrap();
+		let p = connection.p();
+		let statement = formatdoc!(
+			"
+				insert into users (id, email)
+				values ({p}1, {p}2);
+			"
+		);
+		let params = db::params![user, "user@example.com"];
+		connection.execute(statement, params).await.unwrap();
+		let statement = formatdoc!(
+			r#"
+				insert into tokens (id, "user", scopes)
+				values ({p}1, {p}2, {p}3);
+			"#
+		);
+		let params = db::params![token, user, "read unknown"];
+		connection.execute(statement, params).await.unwrap();
+		drop(connection);
+
+		// The unknown scope is skipped.
+		let output = server.get_user(&token).await.unwrap().unwrap();
+		assert_eq!(output.id, user);
+		assert_eq!(
+			output.scopes.into_iter().collect::<Vec<_>>(),
+			[tg::user::Scope::Read]
+		);
+
+		// A deleted token no longer authenticates the user.
+		server.delete_token(&token).await.unwrap();
+		assert!(server.get_user(&token).await.unwrap().is_none());
+	}
+}